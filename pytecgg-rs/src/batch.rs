@@ -0,0 +1,294 @@
+use polars::prelude::*;
+use pyo3::exceptions::PyBaseException;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyTuple};
+use pyo3_polars::PyDataFrame;
+use rayon::prelude::*;
+use rinex::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use crate::filter::ObsFilter;
+use crate::nav::{nav_records, parse_nav_file, parse_msg_types, records_frames, NavRecord};
+use crate::error::polars_error;
+use crate::obs::{check_exists, obs_long_frame, parse_obs_file};
+use crate::scan::SkippedEpoch;
+use crate::time::parse_timescale;
+
+// Paths given either as a list, or as a glob pattern
+fn expand_paths(paths: &Bound<'_, PyAny>) -> PyResult<Vec<String>> {
+    if let Ok(pattern) = paths.extract::<String>() {
+        let entries = glob::glob(&pattern).map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                format!("Invalid glob pattern '{}': {}", pattern, e)
+            )
+        })?;
+        Ok(entries
+            .filter_map(Result::ok)
+            .map(|p| p.to_string_lossy().into_owned())
+            .collect())
+    } else {
+        paths.extract::<Vec<String>>()
+    }
+}
+
+// Station name: MARKER NAME record, or the file name up to the first '.' or '_'
+fn station_name(rinex: &Rinex, path: &Path) -> String {
+    rinex
+        .header
+        .geodetic_marker
+        .as_ref()
+        .map(|marker| marker.name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| {
+            path.file_name()
+                .map(|name| name.to_string_lossy())
+                .unwrap_or_default()
+                .split(['.', '_'])
+                .next()
+                .unwrap_or_default()
+                .to_string()
+        })
+}
+
+// Station name, observations and epochs skipped in lenient mode of one file
+type Station = (String, DataFrame, Vec<SkippedEpoch>);
+
+fn read_station(
+    path: &str,
+    timescale: Option<TimeScale>,
+    filter: &ObsFilter,
+    lenient: bool,
+) -> PyResult<Station> {
+    let path = Path::new(path);
+    check_exists(path)?;
+    let (rinex, skipped) = parse_obs_file(path, lenient)?;
+    let station = station_name(&rinex, path);
+
+    let mut df = obs_long_frame(&rinex, timescale, filter)?;
+    let stations = StringChunked::full("station".into(), &station, df.height()).into_series();
+    df.insert_column(0, stations)
+        .map_err(polars_error)?;
+
+    Ok((station, df, skipped))
+}
+
+#[pyfunction]
+#[pyo3(signature = (
+    paths,
+    concat=true,
+    n_threads=None,
+    timescale=None,
+    constellations=None,
+    svs=None,
+    observables=None,
+    observable_regex=None,
+    start=None,
+    end=None,
+    interval=None,
+    lenient=false,
+))]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn read_rinex_obs_many<'py>(
+    py: Python<'py>,
+    paths: &Bound<'_, PyAny>,
+    concat: bool,
+    n_threads: Option<usize>,
+    timescale: Option<&str>,
+    constellations: Option<Vec<String>>,
+    svs: Option<Vec<String>>,
+    observables: Option<Vec<String>>,
+    observable_regex: Option<&str>,
+    start: Option<&str>,
+    end: Option<&str>,
+    interval: Option<f64>,
+    lenient: bool,
+) -> PyResult<Bound<'py, PyTuple>> {
+    let paths = expand_paths(paths)?;
+    let timescale = timescale.map(parse_timescale).transpose()?;
+    let filter = ObsFilter::new(
+        constellations,
+        svs,
+        observables,
+        observable_regex,
+        start,
+        end,
+        interval,
+    )?;
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(n_threads.unwrap_or(0))
+        .build()
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+    // Files are parsed on Rust threads, without holding the GIL
+    let results: Vec<(String, PyResult<Station>)> = py.allow_threads(|| {
+        pool.install(|| {
+            paths
+                .par_iter()
+                .map(|path| (path.clone(), read_station(path, timescale, &filter, lenient)))
+                .collect()
+        })
+    });
+
+    // Failures are collected per file, as exceptions, rather than aborting the batch
+    let mut frames: BTreeMap<String, DataFrame> = BTreeMap::new();
+    let mut errors = BTreeMap::new();
+    let mut skipped_epochs = BTreeMap::new();
+    for (path, result) in results {
+        match result {
+            Ok((station, df, skipped)) => {
+                let skipped = skipped
+                    .iter()
+                    .map(|s| s.to_dict(py))
+                    .collect::<PyResult<Vec<_>>>()?;
+                skipped_epochs.insert(path, skipped);
+                match frames.get_mut(&station) {
+                    Some(existing) => {
+                        existing.vstack_mut(&df).map_err(polars_error)?;
+                    },
+                    None => {
+                        frames.insert(station, df);
+                    },
+                }
+            },
+            Err(e) => {
+                errors.insert(path, e.into_value(py));
+            },
+        }
+    }
+
+    let output = if concat {
+        let mut frames = frames.into_values();
+        let mut df = frames.next().unwrap_or_default();
+        for other in frames {
+            df.vstack_mut(&other)
+                .map_err(polars_error)?;
+        }
+        PyDataFrame(df).into_pyobject(py)?.into_any()
+    } else {
+        frames
+            .into_iter()
+            .map(|(station, df)| (station, PyDataFrame(df)))
+            .collect::<BTreeMap<_, _>>()
+            .into_pyobject(py)?
+            .into_any()
+    };
+
+    // The epochs skipped in lenient mode come last, per file
+    let mut items = vec![output, errors.into_pyobject(py)?.into_any()];
+    if lenient {
+        items.push(skipped_epochs.into_pyobject(py)?.into_any());
+    }
+    PyTuple::new(py, items)
+}
+
+// Identity of a broadcast ephemeris, as found in consecutive daily files and
+// in merged products: SV, message type, reference time (week and toe, or toc
+// for GLONASS/SBAS), issue of data and, for Galileo, the data source (the
+// same I/NAV ephemeris is broadcast on E1-B and E5b)
+type RecordKey = (String, String, i64, Option<i64>, Option<i64>);
+
+fn record_key(record: &NavRecord) -> RecordKey {
+    let reference = match (record.params.get("week"), record.params.get("toe")) {
+        (Some(week), Some(toe)) => (week * 604_800.0 + toe).round() as i64,
+        _ => record.toc_ns.div_euclid(1_000_000_000),
+    };
+    let issue = ["iode", "iodnav", "aode", "iodec"]
+        .iter()
+        .find_map(|name| record.params.get(*name))
+        .map(|issue| issue.round() as i64);
+    let data_source = record
+        .params
+        .get("dataSrc")
+        .filter(|_| record.sv.starts_with('E'))
+        .map(|source| source.round() as i64);
+    (record.sv.clone(), record.msg_type.clone(), reference, issue, data_source)
+}
+
+// Navigation files merged into one frame per constellation, e.g. the BRDC
+// files of the previous, current and next day. Records sharing a key are
+// kept once; when their content differs, the latest transmitted one wins,
+// then the one of the first file in `paths`. Unreadable files are reported
+// per path, like in `read_rinex_obs_many`.
+#[pyfunction]
+#[pyo3(signature = (paths, msg_types=None, n_threads=None))]
+pub(crate) fn read_rinex_nav_many<'py>(
+    py: Python<'py>,
+    paths: &Bound<'py, PyAny>,
+    msg_types: Option<Vec<String>>,
+    n_threads: Option<usize>,
+) -> PyResult<(BTreeMap<String, PyDataFrame>, Vec<Bound<'py, PyDict>>, BTreeMap<String, Py<PyBaseException>>)> {
+    let paths = expand_paths(paths)?;
+    let msg_types = parse_msg_types(msg_types)?;
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(n_threads.unwrap_or(0))
+        .build()
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+    let results: Vec<PyResult<Vec<NavRecord>>> = py.allow_threads(|| {
+        pool.install(|| {
+            paths
+                .par_iter()
+                .enumerate()
+                .map(|(source, path)| {
+                    let path = Path::new(path);
+                    check_exists(path)?;
+                    let rinex = parse_nav_file(path)?;
+                    Ok(nav_records(&rinex, msg_types.as_ref(), source))
+                })
+                .collect()
+        })
+    });
+
+    let mut kept: Vec<NavRecord> = Vec::new();
+    let mut index: HashMap<RecordKey, usize> = HashMap::new();
+    let mut conflicts = Vec::new();
+    let mut errors = BTreeMap::new();
+
+    for (path, result) in paths.iter().zip(results) {
+        let records = match result {
+            Ok(records) => records,
+            Err(e) => {
+                errors.insert(path.clone(), e.into_value(py));
+                continue;
+            },
+        };
+        for record in records {
+            let key = record_key(&record);
+            let Some(&position) = index.get(&key) else {
+                index.insert(key, kept.len());
+                kept.push(record);
+                continue;
+            };
+
+            let existing = &kept[position];
+            if existing.toc_ns == record.toc_ns && existing.params == record.params {
+                continue;
+            }
+
+            let t_tm = |r: &NavRecord| r.params.get("t_tm").copied().unwrap_or(f64::NEG_INFINITY);
+            let replace = t_tm(&record) > t_tm(existing);
+
+            let conflict = PyDict::new(py);
+            conflict.set_item("sv", &key.0)?;
+            conflict.set_item("msg_type", &key.1)?;
+            conflict.set_item("iod", key.3)?;
+            let (winner, loser) = if replace { (&record, existing) } else { (existing, &record) };
+            conflict.set_item("kept", &paths[winner.source])?;
+            conflict.set_item("dropped", &paths[loser.source])?;
+            conflicts.push(conflict);
+
+            if replace {
+                kept[position] = record;
+            }
+        }
+    }
+
+    kept.sort_by(|a, b| {
+        (a.toc_ns, &a.sv, &a.msg_type, a.source).cmp(&(b.toc_ns, &b.sv, &b.msg_type, b.source))
+    });
+
+    Ok((records_frames(kept, Some(&paths))?, conflicts, errors))
+}
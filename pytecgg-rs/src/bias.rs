@@ -0,0 +1,406 @@
+use polars::prelude::*;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use pyo3_polars::PyDataFrame;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::io::BufRead;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use crate::error::{polars_error, RinexError};
+use crate::header::{field, open_text_reader, parse_f64};
+use crate::obs::check_exists;
+use crate::sp3::time_system_name;
+use crate::time::{calendar_to_nanos, datetime_series_opt, nanos_to_calendar};
+
+const DAY_NS: i64 = 86_400_000_000_000;
+
+// One bias: OSB (one observable) or DSB (obs1 - obs2) of a satellite
+// (`sv`) or of a receiver (`station`, with the constellation in `system`
+// and the DOMES number when given), valid in [start, end) (None when open)
+struct BiasRecord {
+    bias_type: String,
+    svn: Option<String>,
+    sv: Option<String>,
+    station: Option<String>,
+    domes: Option<String>,
+    system: Option<String>,
+    obs1: Option<String>,
+    obs2: Option<String>,
+    start: Option<i64>,
+    end: Option<i64>,
+    unit: String,
+    bias: f64,
+    bias_std: Option<f64>,
+}
+
+fn iso(nanos: Option<i64>) -> Option<String> {
+    let (y, m, d, hh, mm, ss) = nanos_to_calendar(nanos?);
+    Some(format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}", y, m, d, hh, mm, ss))
+}
+
+// Satellite identifier ("G01") or constellation only ("G") of a PRN field
+fn split_prn(prn: Option<String>) -> (Option<String>, Option<String>) {
+    let system = prn.as_deref().and_then(|p| p.get(..1)).map(str::to_string);
+    let sv = prn.filter(|p| p.len() == 3 && p[1..].chars().all(|c| c.is_ascii_digit()));
+    (sv, system)
+}
+
+fn frame(records: &[BiasRecord], time_system: &str) -> PyResult<DataFrame> {
+    let mut df = df![
+        "bias_type" => records.iter().map(|r| r.bias_type.as_str()).collect::<Vec<_>>(),
+        "svn" => records.iter().map(|r| r.svn.as_deref()).collect::<Vec<_>>(),
+        "sv" => records.iter().map(|r| r.sv.as_deref()).collect::<Vec<_>>(),
+        "station" => records.iter().map(|r| r.station.as_deref()).collect::<Vec<_>>(),
+        "domes" => records.iter().map(|r| r.domes.as_deref()).collect::<Vec<_>>(),
+        "system" => records.iter().map(|r| r.system.as_deref()).collect::<Vec<_>>(),
+        "obs1" => records.iter().map(|r| r.obs1.as_deref()).collect::<Vec<_>>(),
+        "obs2" => records.iter().map(|r| r.obs2.as_deref()).collect::<Vec<_>>(),
+        "time_system" => vec![time_system; records.len()],
+        "unit" => records.iter().map(|r| r.unit.as_str()).collect::<Vec<_>>(),
+        "bias" => records.iter().map(|r| r.bias).collect::<Vec<_>>(),
+        "bias_std" => records.iter().map(|r| r.bias_std).collect::<Vec<_>>(),
+    ]
+    .map_err(polars_error)?;
+
+    // Validity window, in the scale given by `time_system`
+    let starts = records.iter().map(|r| r.start).collect();
+    df.insert_column(8, datetime_series_opt("start", starts))
+        .map_err(polars_error)?;
+    let ends = records.iter().map(|r| r.end).collect();
+    df.insert_column(9, datetime_series_opt("end", ends))
+        .map_err(polars_error)?;
+
+    Ok(df)
+}
+
+// Header of a Bias-SINEX file: "%=BIA" line, FILE/REFERENCE, FILE/COMMENT
+// and BIAS/DESCRIPTION blocks
+#[derive(Debug, Default)]
+struct SinexHeader {
+    version: Option<String>,
+    file_agency: Option<String>,
+    created: Option<String>,
+    data_agency: Option<String>,
+    start: Option<String>,
+    end: Option<String>,
+    bias_mode: Option<String>,
+    time_system: String,
+    reference: BTreeMap<String, String>,
+    description: BTreeMap<String, String>,
+    comments: Vec<String>,
+}
+
+impl SinexHeader {
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("version", &self.version)?;
+        dict.set_item("file_agency", &self.file_agency)?;
+        dict.set_item("created", &self.created)?;
+        dict.set_item("data_agency", &self.data_agency)?;
+        dict.set_item("start", &self.start)?;
+        dict.set_item("end", &self.end)?;
+        dict.set_item("bias_mode", &self.bias_mode)?;
+        dict.set_item("time_system", &self.time_system)?;
+        dict.set_item("reference", &self.reference)?;
+        dict.set_item("description", &self.description)?;
+        dict.set_item("comments", &self.comments)?;
+        Ok(dict)
+    }
+}
+
+// "YYYY:DDD:SSSSS" (or "YY:DDD:SSSSS") epoch: Some(None) for the open
+// "0000:000:00000", None when invalid
+fn sinex_epoch(text: &str) -> Option<Option<i64>> {
+    let mut parts = text.trim().split(':').map(|p| p.parse::<i64>().ok());
+    let (Some(Some(year)), Some(Some(doy)), Some(Some(sod)), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return None;
+    };
+    if year == 0 && doy == 0 && sod == 0 {
+        return Some(None);
+    }
+    let year = match year {
+        0..=49 => year + 2000,
+        50..=99 => year + 1900,
+        _ => year,
+    };
+    let new_year = calendar_to_nanos(year as i32, 1, 1, 0, 0, 0, 0);
+    Some(Some(new_year + (doy - 1) * DAY_NS + sod * 1_000_000_000))
+}
+
+// TIME_SYSTEM of the BIAS/DESCRIPTION block: a constellation letter, UTC or TAI
+fn sinex_time_system(value: &str) -> String {
+    let name = match value.trim() {
+        "G" => "GPS",
+        "R" => "GLO",
+        "E" => "GAL",
+        "C" => "BDT",
+        "J" => "QZS",
+        "I" => "IRN",
+        other => other,
+    };
+    time_system_name(Some(name))
+}
+
+fn parse_sinex<R: BufRead>(reader: R) -> Result<(SinexHeader, Vec<BiasRecord>), RinexError> {
+    let mut header = SinexHeader {
+        time_system: "GPST".to_string(),
+        ..Default::default()
+    };
+    let mut records = Vec::new();
+    let mut block = String::new();
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.map_err(|e| RinexError::parse(format!("Bias-SINEX read error: {}", e)))?;
+        let line_number = index + 1;
+
+        if index == 0 {
+            if !line.starts_with("%=BIA") {
+                return Err(RinexError::wrong_type("This is not a Bias-SINEX file"));
+            }
+            // %=BIA version agency creation data_agency start end mode count
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            let token = |i: usize| tokens.get(i).map(|t| t.to_string());
+            let epoch = |i: usize| tokens.get(i).and_then(|t| sinex_epoch(t)).flatten();
+            header.version = token(1);
+            header.file_agency = token(2);
+            header.created = iso(epoch(3));
+            header.data_agency = token(4);
+            header.start = iso(epoch(5));
+            header.end = iso(epoch(6));
+            header.bias_mode = token(7);
+            continue;
+        }
+
+        match line.chars().next() {
+            Some('+') => block = line[1..].trim().to_string(),
+            Some('-') | Some('%') => block.clear(),
+            Some(' ') => match block.as_str() {
+                "FILE/REFERENCE" | "BIAS/DESCRIPTION" => {
+                    // FILE/REFERENCE lines are 1X,A18,1X,A60, BIAS/DESCRIPTION
+                    // keywords take 39 columns
+                    let (key_end, value_start) = if block == "FILE/REFERENCE" { (19, 20) } else { (40, 40) };
+                    let (Some(key), value) = (field(&line, 1, key_end), field(&line, value_start, line.len())) else {
+                        continue;
+                    };
+                    if key == "TIME_SYSTEM" {
+                        header.time_system = sinex_time_system(value.as_deref().unwrap_or(""));
+                    }
+                    let map = if block == "FILE/REFERENCE" { &mut header.reference } else { &mut header.description };
+                    map.insert(key, value.unwrap_or_default());
+                },
+                "FILE/COMMENT" => header.comments.extend(field(&line, 1, line.len())),
+                "BIAS/SOLUTION" => {
+                    let (Some(bias_type), Some(start), Some(end), Some(bias)) = (
+                        field(&line, 1, 5),
+                        line.get(35..49).and_then(sinex_epoch),
+                        line.get(50..64).and_then(sinex_epoch),
+                        parse_f64(&line, 70, 91),
+                    ) else {
+                        return Err(RinexError::parse("Bias-SINEX parsing error: invalid BIAS/SOLUTION record")
+                            .with_line(line_number));
+                    };
+                    let (sv, system) = split_prn(field(&line, 11, 14));
+                    records.push(BiasRecord {
+                        bias_type,
+                        svn: field(&line, 6, 10),
+                        sv,
+                        station: field(&line, 15, 24),
+                        domes: None,
+                        system,
+                        obs1: field(&line, 25, 29),
+                        obs2: field(&line, 30, 34),
+                        start,
+                        end,
+                        unit: field(&line, 65, 69).unwrap_or_default(),
+                        bias,
+                        bias_std: parse_f64(&line, 92, 103),
+                    });
+                },
+                _ => {},
+            },
+            _ => {},
+        }
+    }
+
+    Ok((header, records))
+}
+
+// Header of a CODE DCB file: title line, observable pair and validity window
+#[derive(Debug, Default)]
+struct DcbHeader {
+    title: Option<String>,
+    observables: Option<String>,
+    start: Option<i64>,
+    end: Option<i64>,
+}
+
+impl DcbHeader {
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("title", &self.title)?;
+        dict.set_item("observables", &self.observables)?;
+        dict.set_item("start", iso(self.start))?;
+        dict.set_item("end", iso(self.end))?;
+        dict.set_item("time_system", "GPST")?;
+        Ok(dict)
+    }
+}
+
+// Observable pair ("P1-C1", "P1-P2", "P2-C2") of the title
+fn observables_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\b([PC][12])-([PC][12])\b").unwrap())
+}
+
+// "YEAR 2024, MONTH 10" (monthly) or "30-DAY ... ENDING DAY 281, 2024" titles
+fn window_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"YEAR (\d{4}), MONTH (\d{1,2})|(\d+)-DAY .*ENDING DAY (\d{1,3}), (\d{4})").unwrap()
+    })
+}
+
+fn dcb_window(title: &str) -> (Option<i64>, Option<i64>) {
+    let Some(caps) = window_regex().captures(title) else {
+        return (None, None);
+    };
+    let number = |i: usize| caps.get(i).and_then(|m| m.as_str().parse::<i64>().ok());
+    if let (Some(year), Some(month)) = (number(1), number(2)) {
+        let (next_year, next_month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+        return (
+            Some(calendar_to_nanos(year as i32, month as u8, 1, 0, 0, 0, 0)),
+            Some(calendar_to_nanos(next_year as i32, next_month as u8, 1, 0, 0, 0, 0)),
+        );
+    }
+    if let (Some(days), Some(doy), Some(year)) = (number(3), number(4), number(5)) {
+        let end = calendar_to_nanos(year as i32, 1, 1, 0, 0, 0, 0) + doy * DAY_NS;
+        return (Some(end - days * DAY_NS), Some(end));
+    }
+    (None, None)
+}
+
+// RINEX 3 code of a legacy RINEX 2 observable: P codes are tracked as
+// C1W/C2W by GPS (C1P/C2P by GLONASS) receivers, C/A as C1C/C2C
+fn rinex3_code(system: Option<&str>, legacy: &str) -> String {
+    let p_code = if system == Some("R") { 'P' } else { 'W' };
+    match legacy {
+        "P1" => format!("C1{}", p_code),
+        "P2" => format!("C2{}", p_code),
+        "C1" => "C1C".to_string(),
+        "C2" => "C2C".to_string(),
+        other => other.to_string(),
+    }
+}
+
+// Satellite ("G01 value rms") and receiver ("G ALBH 40129M003 value rms")
+// lines follow the "***" line under the column titles
+fn parse_code_dcb<R: BufRead>(reader: R) -> Result<(DcbHeader, Vec<BiasRecord>), RinexError> {
+    let mut header = DcbHeader::default();
+    let mut pair: Option<(String, String)> = None;
+    let mut records = Vec::new();
+    let mut in_data = false;
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.map_err(|e| RinexError::parse(format!("DCB read error: {}", e)))?;
+        let line_number = index + 1;
+
+        if index == 0 {
+            if !line.contains("DCB") {
+                return Err(RinexError::wrong_type("This is not a CODE DCB file"));
+            }
+            header.title = field(&line, 0, line.len());
+            (header.start, header.end) = dcb_window(&line);
+        }
+        if !in_data {
+            if pair.is_none() {
+                if let Some(caps) = observables_regex().captures(&line) {
+                    header.observables = Some(format!("{}-{}", &caps[1], &caps[2]));
+                    pair = Some((caps[1].to_string(), caps[2].to_string()));
+                }
+            }
+            in_data = line.starts_with("***");
+            continue;
+        }
+
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        if tokens.is_empty() {
+            continue;
+        }
+        let Some((obs1, obs2)) = pair.as_ref() else {
+            return Err(RinexError::parse("DCB parsing error: observable pair not found in the header"));
+        };
+
+        // Bias and, when given, its RMS are the last numeric fields
+        let values: Vec<Option<f64>> = tokens.iter().map(|t| t.parse().ok()).collect();
+        let (bias, bias_std, id) = match values.as_slice() {
+            [.., Some(bias), Some(rms)] if tokens.len() > 2 => (*bias, Some(*rms), &tokens[..tokens.len() - 2]),
+            [.., Some(bias)] if tokens.len() > 1 => (*bias, None, &tokens[..tokens.len() - 1]),
+            _ => {
+                return Err(RinexError::parse("DCB parsing error: invalid bias record").with_line(line_number));
+            },
+        };
+
+        let (sv, system, station, domes) = match id {
+            [prn] if prn.len() == 3 => {
+                let (sv, system) = split_prn(Some(prn.to_string()));
+                (sv, system, None, None)
+            },
+            [system, station, domes @ ..] if system.len() == 1 => {
+                (None, Some(system.to_string()), Some(station.to_string()), domes.first().map(|d| d.to_string()))
+            },
+            [station, domes @ ..] => (None, None, Some(station.to_string()), domes.first().map(|d| d.to_string())),
+            [] => (None, None, None, None),
+        };
+        records.push(BiasRecord {
+            bias_type: "DSB".to_string(),
+            svn: None,
+            obs1: Some(rinex3_code(system.as_deref(), obs1)),
+            obs2: Some(rinex3_code(system.as_deref(), obs2)),
+            sv,
+            station,
+            domes,
+            system,
+            start: header.start,
+            end: header.end,
+            unit: "ns".to_string(),
+            bias,
+            bias_std,
+        });
+    }
+
+    Ok((header, records))
+}
+
+fn open_checked(path: &Path, format: &str) -> PyResult<Box<dyn BufRead + Send>> {
+    check_exists(path)?;
+    open_text_reader(path)
+        .map_err(|e| PyErr::from(RinexError::parse(format!("{} read error: {}", format, e)).with_path(path)))
+}
+
+// Satellite and receiver biases of a Bias-SINEX file (OSB and DSB, plain or
+// gzip-compressed): one row per bias with its observables, validity window
+// and value as written (ns for code biases), and the header
+#[pyfunction]
+pub(crate) fn read_bias_sinex<'py>(py: Python<'py>, path: &str) -> PyResult<(PyDataFrame, Bound<'py, PyDict>)> {
+    let path = Path::new(path);
+    let reader = open_checked(path, "Bias-SINEX")?;
+    let (header, records) = parse_sinex(reader).map_err(|e| e.with_path(path))?;
+
+    Ok((PyDataFrame(frame(&records, &header.time_system)?), header.to_dict(py)?))
+}
+
+// Differential code biases (ns) of a legacy CODE .DCB file (P1-C1, P1-P2 or
+// P2-C2), with the same columns as `read_bias_sinex`: observables are given
+// as RINEX 3 codes and the validity window comes from the title
+#[pyfunction]
+pub(crate) fn read_code_dcb<'py>(py: Python<'py>, path: &str) -> PyResult<(PyDataFrame, Bound<'py, PyDict>)> {
+    let path = Path::new(path);
+    let reader = open_checked(path, "DCB")?;
+    let (header, records) = parse_code_dcb(reader).map_err(|e| e.with_path(path))?;
+
+    Ok((PyDataFrame(frame(&records, "GPST")?), header.to_dict(py)?))
+}
@@ -0,0 +1,38 @@
+use flate2::read::MultiGzDecoder;
+use pyo3::buffer::PyBuffer;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+use rinex::prelude::*;
+use std::io::{BufReader, Cursor, Read};
+
+use crate::error::RinexError;
+
+// Raw content of a Python `bytes` object, buffer (bytearray, memoryview, ...)
+// or binary file-like object, gunzipped if needed
+pub(crate) fn read_buffer(data: &Bound<'_, PyAny>) -> PyResult<Vec<u8>> {
+    let raw = if let Ok(bytes) = data.downcast::<PyBytes>() {
+        bytes.as_bytes().to_vec()
+    } else if data.hasattr("read")? {
+        let content = data.call_method0("read")?;
+        return read_buffer(&content);
+    } else {
+        PyBuffer::<u8>::get(data)?.to_vec(data.py())?
+    };
+
+    if raw.starts_with(&[0x1f, 0x8b]) {
+        let mut inflated = Vec::new();
+        MultiGzDecoder::new(raw.as_slice())
+            .read_to_end(&mut inflated)
+            .map_err(|e| RinexError::parse(format!("Gzip decompression error: {}", e)))?;
+        Ok(inflated)
+    } else {
+        Ok(raw)
+    }
+}
+
+// Parses RINEX content held in memory. Hatanaka-compressed (CRINEX) content
+// is recognised from its header and decompressed by the `rinex` parser.
+pub(crate) fn rinex_from_bytes(content: &[u8]) -> Result<Rinex, RinexError> {
+    Rinex::from_reader(&mut BufReader::new(Cursor::new(content)))
+        .map_err(|e| RinexError::parse(format!("RINEX parsing error: {}", e)))
+}
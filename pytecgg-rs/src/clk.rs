@@ -0,0 +1,227 @@
+use polars::prelude::*;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use pyo3_polars::PyDataFrame;
+use std::io::BufRead;
+use std::path::Path;
+
+use crate::error::{polars_error, RinexError};
+use crate::header::{field, open_text_reader, parse_i32};
+use crate::obs::check_exists;
+use crate::scan::is_end_of_header;
+use crate::sp3::time_system_name;
+use crate::time::{calendar_to_nanos, datetime_series};
+
+// Header records of a clock file
+#[derive(Debug, Default)]
+struct ClkHeader {
+    version: Option<String>,
+    program: Option<String>,
+    run_by: Option<String>,
+    time_system: String,
+    analysis_center: Option<String>,
+    leap_seconds: Option<i32>,
+    trf: Option<String>,
+    stations: Vec<String>,
+    satellites: Vec<String>,
+    comments: Vec<String>,
+}
+
+impl ClkHeader {
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("version", &self.version)?;
+        dict.set_item("program", &self.program)?;
+        dict.set_item("run_by", &self.run_by)?;
+        dict.set_item("time_system", &self.time_system)?;
+        dict.set_item("analysis_center", &self.analysis_center)?;
+        dict.set_item("leap_seconds", self.leap_seconds)?;
+        dict.set_item("trf", &self.trf)?;
+        dict.set_item("stations", &self.stations)?;
+        dict.set_item("satellites", &self.satellites)?;
+        dict.set_item("comments", &self.comments)?;
+        Ok(dict)
+    }
+}
+
+// Clock data record: bias (s) and, when given, its sigma, rate (s/s),
+// rate sigma, acceleration (1/s) and acceleration sigma
+struct ClkRecord {
+    kind: String,
+    name: String,
+    epoch: i64,
+    values: Vec<Option<f64>>,
+}
+
+const VALUES: [&str; 6] = [
+    "bias",
+    "bias_sigma",
+    "rate",
+    "rate_sigma",
+    "acceleration",
+    "acceleration_sigma",
+];
+
+fn parse_header(lines: &[String]) -> Result<ClkHeader, RinexError> {
+    let first = lines.first().map(String::as_str).unwrap_or("");
+    if first.get(20..21) != Some("C") {
+        return Err(RinexError::wrong_type("This is not a RINEX Clock file"));
+    }
+
+    let mut header = ClkHeader {
+        version: field(first, 0, 9),
+        time_system: "GPST".to_string(),
+        ..Default::default()
+    };
+    for line in lines {
+        match line.get(60..).unwrap_or("").trim() {
+            "PGM / RUN BY / DATE" => {
+                header.program = field(line, 0, 20);
+                header.run_by = field(line, 20, 40);
+            },
+            "TIME SYSTEM ID" => header.time_system = time_system_name(field(line, 3, 6).as_deref()),
+            "ANALYSIS CENTER" => header.analysis_center = field(line, 0, 60),
+            "LEAP SECONDS" => header.leap_seconds = parse_i32(line, 0, 6),
+            "# OF SOLN STA / TRF" => header.trf = field(line, 10, 60),
+            // Station name (4 or 9 characters), then its identifier and coordinates
+            "SOLN STA NAME / NUM" => {
+                if let Some(name) = line.split_whitespace().next() {
+                    header.stations.push(name.to_string());
+                }
+            },
+            "PRN LIST" => {
+                header.satellites.extend(line.get(..60).unwrap_or("").split_whitespace().map(str::to_string));
+            },
+            "COMMENT" => {
+                if let Some(comment) = field(line, 0, 60) {
+                    header.comments.push(comment);
+                }
+            },
+            _ => {},
+        }
+    }
+    Ok(header)
+}
+
+// Data records are read as whitespace separated fields, since the width of
+// the name field changed with RINEX 3.04 (4 to 9 characters): type, name,
+// epoch (6 fields), number of values, then the values, continued on the
+// next line beyond the second one
+fn parse_clk<R: BufRead>(reader: R) -> Result<(ClkHeader, Vec<ClkRecord>), RinexError> {
+    let mut header_lines = Vec::new();
+    let mut lines = reader.lines().enumerate();
+
+    for (_, line) in lines.by_ref() {
+        let line = line.map_err(|e| RinexError::parse(format!("RINEX read error: {}", e)))?;
+        let end = is_end_of_header(&line);
+        header_lines.push(line);
+        if end {
+            break;
+        }
+    }
+    if !header_lines.last().is_some_and(|line| is_end_of_header(line)) {
+        if header_lines.first().and_then(|line| line.get(20..21)) != Some("C") {
+            return Err(RinexError::wrong_type("This is not a RINEX Clock file"));
+        }
+        return Err(RinexError::parse("RINEX header is missing END OF HEADER"));
+    }
+    let header = parse_header(&header_lines)?;
+
+    let mut records = Vec::new();
+    while let Some((index, line)) = lines.next() {
+        let line = line.map_err(|e| RinexError::parse(format!("RINEX read error: {}", e)))?;
+        let line_number = index + 1;
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let Some(&kind) = tokens.first() else {
+            continue;
+        };
+        if !matches!(kind, "AS" | "AR" | "CR" | "DR" | "MS") {
+            continue;
+        }
+
+        let corrupted = || {
+            RinexError::corrupted_epoch(
+                "RINEX parsing error: invalid clock data record",
+                line_number,
+                Some(tokens.iter().skip(2).take(6).copied().collect::<Vec<_>>().join(" ")),
+            )
+        };
+        let number = |i: usize| tokens.get(i).and_then(|t| t.parse::<i32>().ok());
+        let (Some(year), Some(month), Some(day), Some(hour), Some(minute)) =
+            (number(2), number(3), number(4), number(5), number(6))
+        else {
+            return Err(corrupted());
+        };
+        let seconds: f64 = tokens.get(7).and_then(|t| t.parse().ok()).ok_or_else(corrupted)?;
+        let count = number(8).ok_or_else(corrupted)? as usize;
+
+        let mut values: Vec<Option<f64>> = tokens[9..]
+            .iter()
+            .map(|t| t.replace(['D', 'd'], "E").parse().ok())
+            .collect();
+        while values.len() < count {
+            let Some((_, next)) = lines.next() else {
+                break;
+            };
+            let next = next.map_err(|e| RinexError::parse(format!("RINEX read error: {}", e)))?;
+            values.extend(next.split_whitespace().map(|t| t.replace(['D', 'd'], "E").parse().ok()));
+        }
+
+        if !matches!(kind, "AS" | "AR") {
+            continue;
+        }
+        records.push(ClkRecord {
+            kind: kind.to_string(),
+            name: tokens[1].to_string(),
+            epoch: calendar_to_nanos(
+                year,
+                month as u8,
+                day as u8,
+                hour as u8,
+                minute as u8,
+                seconds.trunc() as u8,
+                (seconds.fract() * 1e9).round() as u32,
+            ),
+            values,
+        });
+    }
+
+    Ok((header, records))
+}
+
+fn frame(records: &[ClkRecord], header: &ClkHeader) -> PyResult<DataFrame> {
+    let mut df = df![
+        "time_system" => vec![header.time_system.as_str(); records.len()],
+        "type" => records.iter().map(|r| r.kind.as_str()).collect::<Vec<_>>(),
+        "name" => records.iter().map(|r| r.name.as_str()).collect::<Vec<_>>(),
+    ]
+    .map_err(polars_error)?;
+
+    // Epoch as written in the file, in the scale given by `time_system`
+    let epochs = records.iter().map(|r| r.epoch).collect();
+    df.insert_column(0, datetime_series("epoch", epochs))
+        .map_err(polars_error)?;
+
+    for (i, name) in VALUES.iter().enumerate() {
+        let values = records.iter().map(|r| r.values.get(i).copied().flatten());
+        let series = Float64Chunked::from_iter_options((*name).into(), values).into_series();
+        df.with_column(series)
+            .map_err(polars_error)?;
+    }
+
+    Ok(df)
+}
+
+// Satellite (AS) and receiver (AR) clocks of a RINEX clock file (2.x, 3.x,
+// plain or gzip-compressed), and its header
+#[pyfunction]
+pub(crate) fn read_rinex_clk<'py>(py: Python<'py>, path: &str) -> PyResult<(PyDataFrame, Bound<'py, PyDict>)> {
+    let path = Path::new(path);
+    check_exists(path)?;
+
+    let reader = open_text_reader(path)
+        .map_err(|e| RinexError::parse(format!("RINEX read error: {}", e)).with_path(path))?;
+    let (header, records) = parse_clk(reader).map_err(|e| e.with_path(path))?;
+
+    Ok((PyDataFrame(frame(&records, &header)?), header.to_dict(py)?))
+}
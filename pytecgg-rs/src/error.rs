@@ -0,0 +1,137 @@
+use polars::prelude::PolarsError;
+use pyo3::create_exception;
+use pyo3::exceptions::{PyIOError, PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::PyErrArguments;
+use std::path::Path;
+
+// Subclasses of the built-in exceptions raised so far, so that existing
+// `except OSError` / `except ValueError` handlers keep working
+create_exception!(pytecggrs, RinexParseError, PyIOError, "A RINEX file could not be parsed.");
+create_exception!(
+    pytecggrs,
+    CorruptedEpochError,
+    RinexParseError,
+    "An epoch record of a RINEX file could not be parsed."
+);
+create_exception!(
+    pytecggrs,
+    WrongRinexTypeError,
+    PyValueError,
+    "A RINEX file is not of the expected type."
+);
+create_exception!(
+    pytecggrs,
+    DataFrameError,
+    PyRuntimeError,
+    "A DataFrame could not be built out of the parsed records."
+);
+
+pub(crate) fn polars_error(e: PolarsError) -> PyErr {
+    DataFrameError::new_err(e.to_string())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RinexErrorKind {
+    Parse,
+    CorruptedEpoch,
+    WrongType,
+}
+
+// Reading failure, raised in Python as one of the exceptions above with
+// `path`, `line` and `epoch` attributes (None where unknown)
+#[derive(Debug, Clone)]
+pub(crate) struct RinexError {
+    pub kind: RinexErrorKind,
+    pub message: String,
+    pub path: Option<String>,
+    pub line: Option<usize>,
+    pub epoch: Option<String>,
+}
+
+impl RinexError {
+    fn new(kind: RinexErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            path: None,
+            line: None,
+            epoch: None,
+        }
+    }
+
+    pub(crate) fn parse(message: impl Into<String>) -> Self {
+        Self::new(RinexErrorKind::Parse, message)
+    }
+
+    pub(crate) fn corrupted_epoch(message: impl Into<String>, line: usize, epoch: Option<String>) -> Self {
+        Self {
+            line: Some(line),
+            epoch,
+            ..Self::new(RinexErrorKind::CorruptedEpoch, message)
+        }
+    }
+
+    pub(crate) fn wrong_type(message: impl Into<String>) -> Self {
+        Self::new(RinexErrorKind::WrongType, message)
+    }
+
+    pub(crate) fn with_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    pub(crate) fn with_path(mut self, path: &Path) -> Self {
+        self.path = Some(path.display().to_string());
+        self
+    }
+}
+
+impl std::fmt::Display for RinexError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)?;
+        if let Some(path) = &self.path {
+            write!(f, " in {}", path)?;
+        }
+        if let Some(line) = self.line {
+            write!(f, " at line {}", line)?;
+        }
+        if let Some(epoch) = &self.epoch {
+            write!(f, " (epoch {})", epoch)?;
+        }
+        Ok(())
+    }
+}
+
+// The exception instance itself, attributes set, is built when the error is
+// raised: converting a `RinexError` does not need the GIL (e.g. on worker
+// threads)
+impl PyErrArguments for RinexError {
+    fn arguments(self, py: Python<'_>) -> PyObject {
+        let message = self.to_string();
+        let exc = match self.kind {
+            RinexErrorKind::Parse => RinexParseError::new_err(message),
+            RinexErrorKind::CorruptedEpoch => CorruptedEpochError::new_err(message),
+            RinexErrorKind::WrongType => WrongRinexTypeError::new_err(message),
+        };
+        let value = exc.value(py);
+        let attributes = value
+            .setattr("path", self.path)
+            .and_then(|_| value.setattr("line", self.line))
+            .and_then(|_| value.setattr("epoch", self.epoch));
+        match attributes {
+            Ok(()) => value.clone().into_any().unbind(),
+            Err(e) => e.into_value(py).into_any(),
+        }
+    }
+}
+
+impl From<RinexError> for PyErr {
+    fn from(err: RinexError) -> Self {
+        match err.kind {
+            RinexErrorKind::Parse => PyErr::new::<RinexParseError, _>(err),
+            RinexErrorKind::CorruptedEpoch => PyErr::new::<CorruptedEpochError, _>(err),
+            RinexErrorKind::WrongType => PyErr::new::<WrongRinexTypeError, _>(err),
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use pyo3::prelude::*;
+use regex::Regex;
+use rinex::prelude::*;
+use std::str::FromStr;
+
+// Tolerance used when checking epochs against the decimation interval [s]
+const DECIMATION_TOLERANCE: f64 = 1e-3;
+
+// Selection applied while walking the observation record: the record itself
+// is parsed in full, discarded signals are only left out of the output frames
+pub(crate) struct ObsFilter {
+    constellations: Option<Vec<Constellation>>,
+    svs: Option<Vec<SV>>,
+    observables: Option<Vec<String>>,
+    observable_regex: Option<Regex>,
+    start: Option<Epoch>,
+    end: Option<Epoch>,
+    interval: Option<f64>,
+}
+
+fn value_error(msg: String) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyValueError, _>(msg)
+}
+
+fn parse_epoch(epoch: &str) -> PyResult<Epoch> {
+    Epoch::from_str(epoch.trim())
+        .map_err(|e| value_error(format!("Invalid epoch '{}': {}", epoch, e)))
+}
+
+impl ObsFilter {
+    pub(crate) fn new(
+        constellations: Option<Vec<String>>,
+        svs: Option<Vec<String>>,
+        observables: Option<Vec<String>>,
+        observable_regex: Option<&str>,
+        start: Option<&str>,
+        end: Option<&str>,
+        interval: Option<f64>,
+    ) -> PyResult<Self> {
+        let constellations = constellations
+            .map(|list| {
+                list.iter()
+                    .map(|c| {
+                        Constellation::from_str(c)
+                            .map_err(|_| value_error(format!("Unknown constellation: {}", c)))
+                    })
+                    .collect::<PyResult<Vec<_>>>()
+            })
+            .transpose()?;
+
+        let svs = svs
+            .map(|list| {
+                list.iter()
+                    .map(|sv| {
+                        SV::from_str(sv.trim())
+                            .map_err(|_| value_error(format!("Invalid SV identifier: {}", sv)))
+                    })
+                    .collect::<PyResult<Vec<_>>>()
+            })
+            .transpose()?;
+
+        let observable_regex = observable_regex
+            .map(|re| {
+                Regex::new(re)
+                    .map_err(|e| value_error(format!("Invalid observable regex '{}': {}", re, e)))
+            })
+            .transpose()?;
+
+        if let Some(dt) = interval {
+            if dt <= 0.0 || !dt.is_finite() {
+                return Err(value_error(format!("Invalid decimation interval: {}", dt)));
+            }
+        }
+
+        Ok(Self {
+            constellations,
+            svs,
+            observables,
+            observable_regex,
+            start: start.map(parse_epoch).transpose()?,
+            end: end.map(parse_epoch).transpose()?,
+            interval,
+        })
+    }
+
+    // Whether `epoch` lies after the end of the time window
+    pub(crate) fn past_end(&self, epoch: &Epoch) -> bool {
+        self.end.is_some_and(|end| *epoch > end)
+    }
+
+    pub(crate) fn keep_epoch(&self, epoch: &Epoch) -> bool {
+        if self.start.is_some_and(|start| *epoch < start) {
+            return false;
+        }
+        if self.past_end(epoch) {
+            return false;
+        }
+        if let Some(dt) = self.interval {
+            // Keep epochs aligned to multiples of the interval, in GPS time
+            let residual = epoch.to_duration_in_time_scale(TimeScale::GPST).to_seconds() % dt;
+            if residual > DECIMATION_TOLERANCE && dt - residual > DECIMATION_TOLERANCE {
+                return false;
+            }
+        }
+        true
+    }
+
+    pub(crate) fn keep_sv(&self, sv: &SV) -> bool {
+        if let Some(constellations) = &self.constellations {
+            let matches = constellations.iter().any(|c| {
+                *c == sv.constellation || (*c == Constellation::SBAS && sv.constellation.is_sbas())
+            });
+            if !matches {
+                return false;
+            }
+        }
+        if let Some(svs) = &self.svs {
+            if !svs.contains(sv) {
+                return false;
+            }
+        }
+        true
+    }
+
+    pub(crate) fn keep_observable(&self, observable: &str) -> bool {
+        if let Some(observables) = &self.observables {
+            if !observables.iter().any(|o| o == observable) {
+                return false;
+            }
+        }
+        if let Some(re) = &self.observable_regex {
+            if !re.is_match(observable) {
+                return false;
+            }
+        }
+        true
+    }
+}
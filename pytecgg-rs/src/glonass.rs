@@ -0,0 +1,83 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use rinex::prelude::*;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::error::RinexError;
+use crate::header::{open_text_reader, parse_header_text, read_header_text};
+use crate::nav::load_nav_rinex;
+use crate::obs::check_exists;
+
+// Slot -> FDMA channel map from the "GLONASS SLOT / FRQ #" header records
+fn channels_from_obs(path: &Path) -> PyResult<BTreeMap<String, i8>> {
+    check_exists(path)?;
+    let header_text = open_text_reader(path)
+        .and_then(read_header_text)
+        .map_err(|e| RinexError::parse(format!("RINEX header error: {}", e)).with_path(path))?;
+    let rinex = parse_header_text(&header_text).map_err(|e| e.with_path(path))?;
+    Ok(rinex
+        .header
+        .glo_channels
+        .iter()
+        .map(|(sv, channel)| (sv.to_string(), *channel))
+        .collect())
+}
+
+// Slot -> FDMA channel map from the frequency number of GLONASS ephemerides
+// (the most recent ephemeris of each slot wins)
+fn channels_from_nav(path: &Path) -> PyResult<BTreeMap<String, i8>> {
+    let rinex = load_nav_rinex(path)?;
+
+    let mut channels = BTreeMap::new();
+    for (nav_key, ephemeris) in rinex.nav_ephemeris_frames_iter() {
+        if nav_key.sv.constellation != Constellation::Glonass {
+            continue;
+        }
+        if let Some(channel) = ephemeris.orbits.get("channel") {
+            channels.insert(nav_key.sv.to_string(), channel.as_f64().round() as i8);
+        }
+    }
+    Ok(channels)
+}
+
+#[pyfunction]
+#[pyo3(signature = (obs_path=None, nav_path=None))]
+pub(crate) fn read_glonass_channels<'py>(
+    py: Python<'py>,
+    obs_path: Option<&str>,
+    nav_path: Option<&str>,
+) -> PyResult<(BTreeMap<String, i8>, Vec<Bound<'py, PyDict>>)> {
+    if obs_path.is_none() && nav_path.is_none() {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "At least one of obs_path and nav_path is required",
+        ));
+    }
+
+    let obs_channels = obs_path
+        .map(|p| channels_from_obs(Path::new(p)))
+        .transpose()?
+        .unwrap_or_default();
+    let nav_channels = nav_path
+        .map(|p| channels_from_nav(Path::new(p)))
+        .transpose()?
+        .unwrap_or_default();
+
+    // Broadcast values take precedence over the receiver-written header
+    let mut channels = obs_channels.clone();
+    let mut conflicts = Vec::new();
+    for (slot, &nav_channel) in &nav_channels {
+        if let Some(&obs_channel) = obs_channels.get(slot) {
+            if obs_channel != nav_channel {
+                let conflict = PyDict::new(py);
+                conflict.set_item("sv", slot)?;
+                conflict.set_item("obs", obs_channel)?;
+                conflict.set_item("nav", nav_channel)?;
+                conflicts.push(conflict);
+            }
+        }
+        channels.insert(slot.clone(), nav_channel);
+    }
+
+    Ok((channels, conflicts))
+}
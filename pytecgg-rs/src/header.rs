@@ -0,0 +1,248 @@
+use flate2::read::GzDecoder;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use rinex::header::Header;
+use rinex::prelude::*;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Cursor, Read};
+use std::path::Path;
+
+use crate::error::RinexError;
+use crate::scan::is_end_of_header;
+
+// Header records of an observation file the `rinex` crate does not expose
+// (GLONASS code-phase biases and phase shifts), read from the header lines
+#[derive(Debug, Default, Clone)]
+pub(crate) struct HeaderExtras {
+    pub glonass_cod_phs_bis: BTreeMap<String, f64>,
+    pub phase_shifts: Vec<PhaseShift>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct PhaseShift {
+    pub system: String,
+    pub observable: String,
+    pub correction: Option<f64>,
+    pub svs: Vec<String>,
+}
+
+// Fixed-width field of a header line, trimmed (empty fields are None)
+pub(crate) fn field(line: &str, start: usize, end: usize) -> Option<String> {
+    let value = line.get(start..end.min(line.len()))?.trim();
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_string())
+    }
+}
+
+pub(crate) fn parse_f64(line: &str, start: usize, end: usize) -> Option<f64> {
+    field(line, start, end)?.replace(['D', 'd'], "E").parse().ok()
+}
+
+pub(crate) fn parse_i32(line: &str, start: usize, end: usize) -> Option<i32> {
+    field(line, start, end)?.parse().ok()
+}
+
+// Buffered text reader over a plain or gzip-compressed file
+pub(crate) fn open_text_reader(path: &Path) -> std::io::Result<Box<dyn BufRead + Send>> {
+    let mut file = File::open(path)?;
+    let mut magic = [0u8; 2];
+    let gzipped = file.read_exact(&mut magic).is_ok() && magic == [0x1f, 0x8b];
+    drop(file);
+
+    let file = File::open(path)?;
+    if gzipped {
+        Ok(Box::new(BufReader::new(GzDecoder::new(file))))
+    } else {
+        Ok(Box::new(BufReader::new(file)))
+    }
+}
+
+// Whole content of a plain or gzip-compressed text file
+pub(crate) fn read_text(path: &Path) -> std::io::Result<String> {
+    let mut content = String::new();
+    open_text_reader(path)?.read_to_string(&mut content)?;
+    Ok(content)
+}
+
+// Header lines of a file, up to and including END OF HEADER
+pub(crate) fn read_header_text<R: BufRead>(reader: R) -> std::io::Result<String> {
+    let mut header_text = String::new();
+    for line in reader.lines() {
+        let line = line?;
+        header_text.push_str(&line);
+        header_text.push('\n');
+        if is_end_of_header(&line) {
+            break;
+        }
+    }
+    Ok(header_text)
+}
+
+// Parses header lines alone with the `rinex` crate (the record is left empty)
+pub(crate) fn parse_header_text(header_text: &str) -> Result<Rinex, RinexError> {
+    Rinex::from_reader(&mut BufReader::new(Cursor::new(header_text.as_bytes())))
+        .map_err(|e| RinexError::parse(format!("RINEX header error: {}", e)))
+}
+
+impl HeaderExtras {
+    pub(crate) fn from_path(path: &Path) -> std::io::Result<Self> {
+        Self::from_reader(open_text_reader(path)?)
+    }
+
+    pub(crate) fn from_reader<R: BufRead>(reader: R) -> std::io::Result<Self> {
+        let mut extras = Self::default();
+
+        for line in reader.lines() {
+            let line = line?;
+            let label = line.get(60..).unwrap_or("").trim();
+
+            match label {
+                "GLONASS COD/PHS/BIS" => {
+                    for i in 0..4 {
+                        let start = 13 * i;
+                        if let (Some(code), Some(bias)) =
+                            (field(&line, start + 1, start + 4), parse_f64(&line, start + 5, start + 13))
+                        {
+                            extras.glonass_cod_phs_bis.insert(code, bias);
+                        }
+                    }
+                },
+                "SYS / PHASE SHIFT" => {
+                    let svs: Vec<String> = (0..10)
+                        .filter_map(|i| field(&line, 19 + 4 * i, 22 + 4 * i))
+                        .collect();
+                    match field(&line, 0, 1) {
+                        Some(system) => extras.phase_shifts.push(PhaseShift {
+                            system,
+                            observable: field(&line, 2, 5).unwrap_or_default(),
+                            correction: parse_f64(&line, 6, 14),
+                            svs,
+                        }),
+                        // Continuation line of the satellite list
+                        None => {
+                            if let Some(last) = extras.phase_shifts.last_mut() {
+                                last.svs.extend(svs);
+                            }
+                        },
+                    }
+                },
+                "END OF HEADER" => break,
+                _ => {},
+            }
+        }
+
+        Ok(extras)
+    }
+}
+
+// System letter of a constellation, as in RINEX headers ("M" for mixed files,
+// "S" for any SBAS)
+fn constellation_letter(constellation: Constellation) -> String {
+    let letter = match constellation {
+        Constellation::GPS => "G",
+        Constellation::Glonass => "R",
+        Constellation::Galileo => "E",
+        Constellation::BeiDou => "C",
+        Constellation::QZSS => "J",
+        Constellation::IRNSS => "I",
+        Constellation::Mixed => "M",
+        c if c.is_sbas() => "S",
+        c => return c.to_string(),
+    };
+    letter.to_string()
+}
+
+// Whole string of a header field, trimmed (empty fields are None)
+fn non_empty(value: &str) -> Option<String> {
+    field(value, 0, value.len())
+}
+
+// Header metadata of an observation file, as a dict: `rinex.header`,
+// completed by the records only found in the header lines
+pub(crate) fn obs_header_dict<'py>(
+    py: Python<'py>,
+    header: &Header,
+    extras: &HeaderExtras,
+) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("version", header.version.to_string())?;
+    dict.set_item("system", header.constellation.map(constellation_letter))?;
+    dict.set_item("program", &header.program)?;
+    dict.set_item("run_by", &header.run_by)?;
+
+    let marker = header.geodetic_marker.as_ref();
+    dict.set_item("marker_name", marker.and_then(|m| non_empty(&m.name)))?;
+    dict.set_item("marker_number", marker.and_then(|m| m.number()))?;
+    dict.set_item(
+        "marker_type",
+        marker
+            .and_then(|m| m.marker_type.as_ref())
+            .map(|t| format!("{:?}", t).to_uppercase()),
+    )?;
+    dict.set_item("observer", &header.observer)?;
+    dict.set_item("agency", &header.agency)?;
+
+    let receiver = header.rcvr.as_ref();
+    dict.set_item("receiver_number", receiver.and_then(|r| non_empty(&r.sn)))?;
+    dict.set_item("receiver_type", receiver.and_then(|r| non_empty(&r.model)))?;
+    dict.set_item("receiver_firmware", receiver.and_then(|r| non_empty(&r.firmware)))?;
+
+    let antenna = header.rcvr_antenna.as_ref();
+    dict.set_item("antenna_number", antenna.and_then(|a| non_empty(&a.sn)))?;
+    dict.set_item("antenna_type", antenna.and_then(|a| non_empty(&a.model)))?;
+    dict.set_item("rx_position", header.rx_position)?;
+    dict.set_item(
+        "antenna_delta_hen",
+        antenna.and_then(|a| Some((a.height?, a.eastern?, a.northern?))),
+    )?;
+
+    let obs = header.obs.as_ref();
+    let obs_types: BTreeMap<String, Vec<String>> = obs
+        .map(|obs| {
+            obs.codes
+                .iter()
+                .map(|(constellation, codes)| {
+                    (
+                        constellation_letter(*constellation),
+                        codes.iter().map(|code| code.to_string()).collect(),
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    dict.set_item("obs_types", obs_types)?;
+    dict.set_item("interval", header.sampling_interval.map(|dt| dt.to_seconds()))?;
+    dict.set_item(
+        "time_of_first_obs",
+        obs.and_then(|obs| obs.timeof_first_obs).map(|t| t.to_string()),
+    )?;
+    dict.set_item(
+        "time_of_last_obs",
+        obs.and_then(|obs| obs.timeof_last_obs).map(|t| t.to_string()),
+    )?;
+    dict.set_item("leap_seconds", header.leap.as_ref().map(|leap| leap.leap))?;
+
+    let glonass_slots: BTreeMap<String, i8> = header
+        .glo_channels
+        .iter()
+        .map(|(sv, channel)| (sv.to_string(), *channel))
+        .collect();
+    dict.set_item("glonass_slots", glonass_slots)?;
+    dict.set_item("glonass_cod_phs_bis", &extras.glonass_cod_phs_bis)?;
+
+    let phase_shifts = PyList::empty(py);
+    for shift in &extras.phase_shifts {
+        let item = PyDict::new(py);
+        item.set_item("system", &shift.system)?;
+        item.set_item("observable", &shift.observable)?;
+        item.set_item("correction", shift.correction)?;
+        item.set_item("svs", &shift.svs)?;
+        phase_shifts.append(item)?;
+    }
+    dict.set_item("phase_shifts", phase_shifts)?;
+
+    Ok(dict)
+}
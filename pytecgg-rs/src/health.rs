@@ -0,0 +1,157 @@
+use polars::prelude::*;
+use pyo3::prelude::*;
+use pyo3_polars::PyDataFrame;
+
+use crate::error::polars_error;
+use crate::select::{opt_f64_column, str_column};
+
+// Galileo signal health status (2 bits)
+fn galileo_hs(status: u32) -> &'static str {
+    match status {
+        1 => "out of service",
+        2 => "will be out of service",
+        _ => "in test",
+    }
+}
+
+// Galileo health field: DVS (1 bit) and HS (2 bits) for E1B (bits 0-2), E5a
+// (bits 3-5) and E5b (bits 6-8). I/NAV only carries E1B and E5b, F/NAV E5a.
+fn galileo_issues(health: u32, msg_type: Option<&str>) -> Vec<String> {
+    let signals: &[(&str, u32)] = match msg_type {
+        Some("INAV") => &[("E1B", 0), ("E5b", 6)],
+        Some("FNAV") => &[("E5a", 3)],
+        _ => &[("E1B", 0), ("E5a", 3), ("E5b", 6)],
+    };
+    let mut issues = Vec::new();
+    for (signal, shift) in signals {
+        let status = (health >> (shift + 1)) & 0b11;
+        if status != 0 {
+            issues.push(format!("{} HS {}", signal, galileo_hs(status)));
+        }
+        if (health >> shift) & 1 != 0 {
+            issues.push(format!("{} DVS working without guarantee", signal));
+        }
+    }
+    issues
+}
+
+// Health flags of one record, as read in the navigation frames
+struct HealthFields {
+    health: Option<f64>,
+    sat_h1: Option<f64>,
+    accuracy: Option<f64>,
+    sisa: Option<f64>,
+}
+
+// Reasons why a record should not be used, given the SV letter and message type
+fn record_issues(
+    letter: char,
+    msg_type: Option<&str>,
+    fields: &HealthFields,
+    max_accuracy: Option<f64>,
+) -> Vec<String> {
+    let mut issues = Vec::new();
+    let health = fields.health.map(|h| h as u32).filter(|h| *h != 0);
+
+    match letter {
+        // 6-bit SV health: MSB set when some or all navigation data are bad
+        'G' | 'J' | 'I' => {
+            if let Some(h) = health {
+                let scope = if h & 0x20 != 0 { "NAV data" } else { "signal" };
+                issues.push(format!("SV health 0x{:02X} ({})", h, scope));
+            }
+        },
+        'E' => {
+            if let Some(h) = health {
+                issues.extend(galileo_issues(h, msg_type));
+            }
+        },
+        // Autonomous satellite health flag (0: good, 1: not good)
+        'C' => {
+            if fields.sat_h1.or(fields.health).is_some_and(|h| h != 0.0) {
+                issues.push("SatH1 not good".to_string());
+            }
+        },
+        // Most significant bit of Bn
+        'R' => {
+            if health.is_some() {
+                issues.push("Bn unhealthy".to_string());
+            }
+        },
+        _ => {
+            if let Some(h) = health {
+                issues.push(format!("health {}", h));
+            }
+        },
+    }
+
+    // SISA (Galileo, -1 when no accuracy prediction is available) or URA (m)
+    let (name, accuracy) = match letter {
+        'E' => ("SISA", fields.sisa),
+        _ => ("accuracy", fields.accuracy),
+    };
+    match accuracy {
+        Some(value) if value < 0.0 || (letter != 'E' && value >= 6144.0) => {
+            issues.push(format!("{} no accuracy prediction available", name));
+        },
+        Some(value) if max_accuracy.is_some_and(|max| value > max) => {
+            issues.push(format!("{} {} m above {} m", name, value, max_accuracy.unwrap_or_default()));
+        },
+        _ => {},
+    }
+
+    issues
+}
+
+// Screening of every record of a navigation frame: the reasons why it should
+// not be used, joined with "; ", or None when it can be used
+pub(crate) fn screen_records(nav: &DataFrame, max_accuracy: Option<f64>) -> PyResult<Vec<Option<String>>> {
+    let svs = str_column(nav, "sv")?;
+    let msg_types = match nav.column("msg_type") {
+        Ok(_) => str_column(nav, "msg_type")?,
+        Err(_) => vec![None; nav.height()],
+    };
+    let health = opt_f64_column(nav, "health")?;
+    let sat_h1 = opt_f64_column(nav, "satH1")?;
+    let accuracy = opt_f64_column(nav, "accuracy")?;
+    let sisa = opt_f64_column(nav, "sisa")?;
+
+    Ok((0..nav.height())
+        .map(|row| {
+            let letter = svs[row].as_deref().and_then(|sv| sv.chars().next()).unwrap_or(' ');
+            let fields = HealthFields {
+                health: health[row],
+                sat_h1: sat_h1[row],
+                accuracy: accuracy[row],
+                sisa: sisa[row],
+            };
+            let issues = record_issues(letter, msg_types[row].as_deref(), &fields, max_accuracy);
+            (!issues.is_empty()).then(|| issues.join("; "))
+        })
+        .collect())
+}
+
+// Health and accuracy screening of a navigation frame (as returned by
+// `read_rinex_nav`), with the semantics of each constellation: adds
+// "healthy" and "health_issue" columns, or drops the unusable records
+#[pyfunction]
+#[pyo3(signature = (nav, drop=false, max_accuracy=None))]
+pub(crate) fn screen_nav(nav: PyDataFrame, drop: bool, max_accuracy: Option<f64>) -> PyResult<PyDataFrame> {
+    let mut nav: DataFrame = nav.into();
+    let issues = screen_records(&nav, max_accuracy)?;
+    let healthy: BooleanChunked = issues.iter().map(|issue| Some(issue.is_none())).collect();
+
+    if drop {
+        let nav = nav
+            .filter(&healthy)
+            .map_err(polars_error)?;
+        return Ok(PyDataFrame(nav));
+    }
+
+    nav.with_column(healthy.with_name("healthy".into()).into_series())
+        .map_err(polars_error)?;
+    nav.with_column(Series::new("health_issue".into(), issues))
+        .map_err(polars_error)?;
+
+    Ok(PyDataFrame(nav))
+}
@@ -0,0 +1,343 @@
+use polars::prelude::*;
+use pyo3::prelude::*;
+use pyo3_polars::PyDataFrame;
+use std::collections::{HashMap, HashSet};
+use std::ffi::CString;
+
+use crate::error::polars_error;
+use crate::select::{datetime_column, opt_f64_column, str_column};
+use crate::time::{datetime_series, gpst_offset_ns};
+
+const NS: f64 = 1e9;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Method {
+    Lagrange,
+    Chebyshev,
+}
+
+fn parse_method(name: &str) -> PyResult<Method> {
+    match name.trim().to_lowercase().as_str() {
+        "lagrange" => Ok(Method::Lagrange),
+        "chebyshev" => Ok(Method::Chebyshev),
+        _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            format!("Unsupported interpolation method: {} (expected lagrange or chebyshev)", name)
+        )),
+    }
+}
+
+// SP3 record of one satellite: epoch (GPST ns), position (m) and clock (s)
+struct Node {
+    t: i64,
+    pos: [f64; 3],
+    clock: Option<f64>,
+}
+
+// Nodes of one satellite, sorted and split into arcs where consecutive epochs
+// are more than `max_gap` apart (by default, twice the sampling interval).
+// Epochs found twice (e.g. midnight in consecutive daily files) are kept once.
+fn split_arcs(mut nodes: Vec<Node>, max_gap_ns: Option<i64>) -> Vec<Vec<Node>> {
+    nodes.sort_by_key(|node| node.t);
+    nodes.dedup_by_key(|node| node.t);
+
+    let interval = nodes.windows(2).map(|w| w[1].t - w[0].t).min();
+    let max_gap = max_gap_ns.or(interval.map(|i| 2 * i)).unwrap_or(i64::MAX);
+
+    let mut arcs: Vec<Vec<Node>> = Vec::new();
+    for node in nodes {
+        let same_arc = arcs
+            .last()
+            .and_then(|arc| arc.last())
+            .is_some_and(|last| node.t - last.t <= max_gap);
+        if !same_arc {
+            arcs.push(Vec::new());
+        }
+        if let Some(arc) = arcs.last_mut() {
+            arc.push(node);
+        }
+    }
+    arcs
+}
+
+// Weights giving the value and the time derivative at t = 0 of the Lagrange
+// polynomial through nodes at times `ts` (s)
+fn lagrange_weights(ts: &[f64]) -> (Vec<f64>, Vec<f64>) {
+    let n = ts.len();
+    // Factor (0 - t_m) / (t_j - t_m) of the basis polynomial j
+    let factor = |j: usize, m: usize| -ts[m] / (ts[j] - ts[m]);
+    let weights = (0..n)
+        .map(|j| (0..n).filter(|m| *m != j).map(|m| factor(j, m)).product())
+        .collect();
+    let rates = (0..n)
+        .map(|j| {
+            (0..n)
+                .filter(|i| *i != j)
+                .map(|i| {
+                    (0..n)
+                        .filter(|m| *m != i && *m != j)
+                        .map(|m| factor(j, m))
+                        .product::<f64>()
+                        / (ts[j] - ts[i])
+                })
+                .sum()
+        })
+        .collect();
+    (weights, rates)
+}
+
+// Chebyshev polynomials T_0..T_degree and their derivatives at u
+fn chebyshev_basis(u: f64, degree: usize) -> (Vec<f64>, Vec<f64>) {
+    let mut t = vec![1.0, u];
+    let mut dt = vec![0.0, 1.0];
+    for k in 1..degree {
+        t.push(2.0 * u * t[k] - t[k - 1]);
+        dt.push(2.0 * t[k] + 2.0 * u * dt[k] - dt[k - 1]);
+    }
+    t.truncate(degree + 1);
+    dt.truncate(degree + 1);
+    (t, dt)
+}
+
+// Solution of a small linear system, by Gaussian elimination with partial pivoting
+#[allow(clippy::needless_range_loop)]
+fn solve(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+    for col in 0..n {
+        let pivot = (col..n).max_by(|i, j| a[*i][col].abs().total_cmp(&a[*j][col].abs()))?;
+        if a[pivot][col].abs() < 1e-300 {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        for row in col + 1..n {
+            let factor = a[row][col] / a[col][col];
+            for k in col..n {
+                a[row][k] -= factor * a[col][k];
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let sum: f64 = (row + 1..n).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - sum) / a[row][row];
+    }
+    Some(x)
+}
+
+// Weights giving the value and the time derivative at t = 0 of the least
+// squares Chebyshev fit of the given degree through nodes at times `ts` (s).
+// With fit coefficients c = (AᵀA)⁻¹Aᵀy, the value T(0)·c is w·y with
+// w = A (AᵀA)⁻¹ T(0).
+fn chebyshev_weights(ts: &[f64], degree: usize) -> Option<(Vec<f64>, Vec<f64>)> {
+    let n = ts.len();
+    if n == 1 {
+        return Some((vec![1.0], vec![0.0]));
+    }
+    let (first, last) = (ts[0], ts[n - 1]);
+    let scale = 2.0 / (last - first);
+    let to_u = |t: f64| (t - first) * scale - 1.0;
+
+    let rows: Vec<Vec<f64>> = ts.iter().map(|t| chebyshev_basis(to_u(*t), degree).0).collect();
+    let normal: Vec<Vec<f64>> = (0..=degree)
+        .map(|i| (0..=degree).map(|j| rows.iter().map(|r| r[i] * r[j]).sum()).collect())
+        .collect();
+
+    let (basis, basis_rate) = chebyshev_basis(to_u(0.0), degree);
+    let z = solve(normal.clone(), basis)?;
+    let z_rate = solve(normal, basis_rate.iter().map(|d| d * scale).collect())?;
+
+    let weights = rows.iter().map(|r| r.iter().zip(&z).map(|(a, b)| a * b).sum()).collect();
+    let rates = rows.iter().map(|r| r.iter().zip(&z_rate).map(|(a, b)| a * b).sum()).collect();
+    Some((weights, rates))
+}
+
+// Interpolated state of one satellite at one epoch
+struct State {
+    pos: [f64; 3],
+    vel: [f64; 3],
+    clock: Option<f64>,
+    edge: bool,
+}
+
+// State at `target` from the arc containing it: `nodes` SP3 epochs around
+// the target (fewer at the edges of short arcs). `edge` is set when the
+// window could not be centred on the target.
+fn interpolate(
+    arcs: &[Vec<Node>],
+    target: i64,
+    method: Method,
+    order: usize,
+    nodes: usize,
+) -> Option<State> {
+    let arc = arcs
+        .iter()
+        .find(|arc| arc.first().is_some_and(|n| n.t <= target) && arc.last().is_some_and(|n| n.t >= target))?;
+
+    let n = arc.len();
+    let size = nodes.min(n);
+    let k = arc.partition_point(|node| node.t < target);
+    let ideal = k as isize - (nodes / 2) as isize;
+    let start = ideal.clamp(0, (n - size) as isize) as usize;
+    let edge = size < nodes || start as isize != ideal;
+
+    let window = &arc[start..start + size];
+    let ts: Vec<f64> = window.iter().map(|node| (node.t - target) as f64 / NS).collect();
+    let (weights, rates) = match method {
+        Method::Lagrange => lagrange_weights(&ts),
+        Method::Chebyshev => chebyshev_weights(&ts, order.min(size - 1))?,
+    };
+
+    let mut pos = [0.0; 3];
+    let mut vel = [0.0; 3];
+    for ((node, weight), rate) in window.iter().zip(&weights).zip(&rates) {
+        for (axis, coordinate) in node.pos.iter().enumerate() {
+            pos[axis] += weight * coordinate;
+            vel[axis] += rate * coordinate;
+        }
+    }
+
+    // Clock: linear interpolation between the surrounding epochs
+    let clock = match arc.get(k) {
+        Some(node) if node.t == target => node.clock,
+        Some(next) if k > 0 => {
+            let previous = &arc[k - 1];
+            let fraction = (target - previous.t) as f64 / (next.t - previous.t) as f64;
+            previous.clock.zip(next.clock).map(|(a, b)| a + (b - a) * fraction)
+        },
+        _ => None,
+    };
+
+    Some(State { pos, vel, clock, edge })
+}
+
+// Epochs of a frame in GPST nanoseconds, using its "time_system" column if any
+fn gpst_epochs(df: &DataFrame) -> PyResult<Vec<Option<i64>>> {
+    let epochs = datetime_column(df, "epoch")?;
+    let time_systems = match df.column("time_system") {
+        Ok(_) => str_column(df, "time_system")?,
+        Err(_) => vec![None; df.height()],
+    };
+    epochs
+        .into_iter()
+        .zip(time_systems)
+        .map(|(epoch, ts)| {
+            epoch
+                .map(|e| Ok(e + gpst_offset_ns(ts.as_deref().unwrap_or("GPST"), e)?))
+                .transpose()
+        })
+        .collect()
+}
+
+// Precise orbits interpolated at the distinct (epoch, sv) pairs of `epochs`
+// (e.g. an observation frame): ECEF positions (m), velocities (m/s, from the
+// derivative of the interpolating polynomial) and clocks (s, linear).
+// `sp3` may gather consecutive daily files; arcs are split at data gaps and
+// epochs outside them are left empty. Epochs interpolated with a window that
+// could not be centred (edge of an arc) are flagged and reported by a warning.
+#[pyfunction]
+#[pyo3(signature = (sp3, epochs, method="lagrange", order=10, nodes=None, max_gap=None))]
+pub(crate) fn interpolate_sp3(
+    py: Python<'_>,
+    sp3: PyDataFrame,
+    epochs: PyDataFrame,
+    method: &str,
+    order: usize,
+    nodes: Option<usize>,
+    max_gap: Option<f64>,
+) -> PyResult<PyDataFrame> {
+    let method = parse_method(method)?;
+    let nodes = nodes.unwrap_or(order + 1);
+    if nodes < order + 1 || (method == Method::Lagrange && nodes != order + 1) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Invalid number of nodes {} for a {} interpolation of order {}",
+            nodes,
+            if method == Method::Lagrange { "Lagrange" } else { "Chebyshev" },
+            order
+        )));
+    }
+    let sp3: DataFrame = sp3.into();
+    let epochs: DataFrame = epochs.into();
+
+    // SP3 nodes, split into arcs per satellite
+    let sp3_epochs = gpst_epochs(&sp3)?;
+    let sp3_svs = str_column(&sp3, "sv")?;
+    let coordinates = [
+        opt_f64_column(&sp3, "x")?,
+        opt_f64_column(&sp3, "y")?,
+        opt_f64_column(&sp3, "z")?,
+    ];
+    let clocks = opt_f64_column(&sp3, "clock")?;
+
+    let mut by_sv: HashMap<String, Vec<Node>> = HashMap::new();
+    for (row, (epoch, sv)) in sp3_epochs.into_iter().zip(sp3_svs).enumerate() {
+        let (Some(t), Some(sv)) = (epoch, sv) else {
+            continue;
+        };
+        let (Some(x), Some(y), Some(z)) = (coordinates[0][row], coordinates[1][row], coordinates[2][row]) else {
+            continue;
+        };
+        by_sv.entry(sv).or_default().push(Node {
+            t,
+            pos: [x, y, z],
+            clock: clocks[row],
+        });
+    }
+    let max_gap_ns = max_gap.map(|gap| (gap * NS) as i64);
+    let arcs: HashMap<String, Vec<Vec<Node>>> = by_sv
+        .into_iter()
+        .map(|(sv, nodes)| (sv, split_arcs(nodes, max_gap_ns)))
+        .collect();
+
+    // Distinct (epoch, sv) pairs, in order of appearance
+    let targets = gpst_epochs(&epochs)?;
+    let epoch_values = datetime_column(&epochs, "epoch")?;
+    let epoch_svs = str_column(&epochs, "sv")?;
+
+    let mut seen = HashSet::new();
+    let mut out_epochs = Vec::new();
+    let mut out_svs = Vec::new();
+    let mut states = Vec::new();
+    for ((target, epoch), sv) in targets.into_iter().zip(epoch_values).zip(epoch_svs) {
+        let (Some(target), Some(epoch), Some(sv)) = (target, epoch, sv) else {
+            continue;
+        };
+        if !seen.insert((target, sv.clone())) {
+            continue;
+        }
+        let state = arcs
+            .get(&sv)
+            .and_then(|arcs| interpolate(arcs, target, method, order, nodes));
+        out_epochs.push(epoch);
+        out_svs.push(sv);
+        states.push(state);
+    }
+
+    let edges = states.iter().filter(|s| s.as_ref().is_some_and(|s| s.edge)).count();
+    if edges > 0 {
+        let message = format!(
+            "{} epochs interpolated at the edge of an SP3 arc (window of {} nodes not centred)",
+            edges, nodes
+        );
+        let message = CString::new(message).unwrap_or_default();
+        PyErr::warn(py, py.get_type::<pyo3::exceptions::PyRuntimeWarning>().as_any(), &message, 1)?;
+    }
+
+    let column = |f: fn(&State) -> Option<f64>| states.iter().map(|s| s.as_ref().and_then(f)).collect::<Vec<_>>();
+    let mut df = df![
+        "sv" => &out_svs,
+        "x" => column(|s| Some(s.pos[0])),
+        "y" => column(|s| Some(s.pos[1])),
+        "z" => column(|s| Some(s.pos[2])),
+        "vx" => column(|s| Some(s.vel[0])),
+        "vy" => column(|s| Some(s.vel[1])),
+        "vz" => column(|s| Some(s.vel[2])),
+        "clock" => column(|s| s.clock),
+        "edge" => states.iter().map(|s| s.as_ref().map(|s| s.edge)).collect::<Vec<_>>(),
+    ]
+    .map_err(polars_error)?;
+    df.insert_column(0, datetime_series("epoch", out_epochs))
+        .map_err(polars_error)?;
+
+    Ok(PyDataFrame(df))
+}
@@ -0,0 +1,702 @@
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use polars::prelude::*;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use pyo3_polars::PyDataFrame;
+use rinex::prelude::*;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufRead, Write};
+use std::path::Path;
+
+use crate::error::{polars_error, RinexError};
+use crate::header::{field, open_text_reader, parse_f64, parse_i32};
+use crate::obs::check_exists;
+use crate::select::{datetime_column, opt_f64_column, str_column};
+use crate::time::{datetime_series, epoch_to_nanos, nanos_to_calendar};
+
+// Missing value of TEC, RMS and height maps
+const MISSING: i32 = 9999;
+
+// Header records of an IONEX file
+#[derive(Debug, Default)]
+struct IonexHeader {
+    version: Option<String>,
+    system: Option<String>,
+    program: Option<String>,
+    run_by: Option<String>,
+    description: Vec<String>,
+    first_epoch: Option<String>,
+    last_epoch: Option<String>,
+    interval: Option<i32>,
+    num_maps: Option<i32>,
+    mapping_function: Option<String>,
+    elevation_cutoff: Option<f64>,
+    observables: Option<String>,
+    num_stations: Option<i32>,
+    num_satellites: Option<i32>,
+    base_radius: Option<f64>,
+    map_dimension: Option<i32>,
+    heights: [Option<f64>; 3],
+    latitudes: [Option<f64>; 3],
+    longitudes: [Option<f64>; 3],
+    exponent: i32,
+    comments: Vec<String>,
+}
+
+// Differential code bias of a satellite or station (ns), from the auxiliary data
+struct Dcb {
+    kind: &'static str,
+    system: Option<String>,
+    name: String,
+    domes: Option<String>,
+    bias: Option<f64>,
+    rms: Option<f64>,
+}
+
+// Epoch records (6I6) as ISO 8601 strings
+fn epoch_fields(line: &str) -> Option<[i32; 6]> {
+    let mut fields = [0; 6];
+    for (i, value) in fields.iter_mut().enumerate() {
+        *value = parse_i32(line, 6 * i, 6 * i + 6)?;
+    }
+    Some(fields)
+}
+
+fn epoch_string(line: &str) -> Option<String> {
+    let [y, m, d, hh, mm, ss] = epoch_fields(line)?;
+    Some(format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}", y, m, d, hh, mm, ss))
+}
+
+// Grid definition records ("2X,3F6.1")
+fn grid_definition(line: &str) -> [Option<f64>; 3] {
+    [parse_f64(line, 2, 8), parse_f64(line, 8, 14), parse_f64(line, 14, 20)]
+}
+
+// Grid axis from its definition record (first, last, step)
+fn grid_axis([first, last, step]: [Option<f64>; 3]) -> Option<Vec<f64>> {
+    let (first, last, step) = (first?, last?, step?);
+    if step == 0.0 {
+        return Some(vec![first]);
+    }
+    let count = ((last - first) / step).round();
+    if count < 0.0 {
+        return None;
+    }
+    Some((0..=count as usize).map(|k| first + k as f64 * step).collect())
+}
+
+// Grid point key, to the tenth of a degree (or km)
+fn grid_key(epoch: i64, lat: f64, lon: f64, height: f64) -> (i64, i64, i64, i64) {
+    let tenth = |v: f64| (v * 10.0).round() as i64;
+    (epoch, tenth(lat), tenth(lon), tenth(height))
+}
+
+impl IonexHeader {
+    fn parse_line(&mut self, line: &str, label: &str) {
+        match label {
+            "IONEX VERSION / TYPE" => {
+                self.version = field(line, 0, 8);
+                self.system = field(line, 40, 43);
+            },
+            "PGM / RUN BY / DATE" => {
+                self.program = field(line, 0, 20);
+                self.run_by = field(line, 20, 40);
+            },
+            "DESCRIPTION" => self.description.extend(field(line, 0, 60)),
+            "EPOCH OF FIRST MAP" => self.first_epoch = epoch_string(line),
+            "EPOCH OF LAST MAP" => self.last_epoch = epoch_string(line),
+            "INTERVAL" => self.interval = parse_i32(line, 0, 6),
+            "# OF MAPS IN FILE" => self.num_maps = parse_i32(line, 0, 6),
+            "MAPPING FUNCTION" => self.mapping_function = field(line, 2, 6),
+            "ELEVATION CUTOFF" => self.elevation_cutoff = parse_f64(line, 0, 8),
+            "OBSERVABLES USED" => self.observables = field(line, 0, 60),
+            "# OF STATIONS" => self.num_stations = parse_i32(line, 0, 6),
+            "# OF SATELLITES" => self.num_satellites = parse_i32(line, 0, 6),
+            "BASE RADIUS" => self.base_radius = parse_f64(line, 0, 8),
+            "MAP DIMENSION" => self.map_dimension = parse_i32(line, 0, 6),
+            "HGT1 / HGT2 / DHGT" => self.heights = grid_definition(line),
+            "LAT1 / LAT2 / DLAT" => self.latitudes = grid_definition(line),
+            "LON1 / LON2 / DLON" => self.longitudes = grid_definition(line),
+            "EXPONENT" => self.exponent = parse_i32(line, 0, 6).unwrap_or(-1),
+            "COMMENT" => self.comments.extend(field(line, 0, 60)),
+            _ => {},
+        }
+    }
+
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("version", &self.version)?;
+        dict.set_item("system", &self.system)?;
+        dict.set_item("program", &self.program)?;
+        dict.set_item("run_by", &self.run_by)?;
+        dict.set_item("description", &self.description)?;
+        dict.set_item("first_epoch", &self.first_epoch)?;
+        dict.set_item("last_epoch", &self.last_epoch)?;
+        dict.set_item("interval", self.interval)?;
+        dict.set_item("num_maps", self.num_maps)?;
+        dict.set_item("mapping_function", &self.mapping_function)?;
+        dict.set_item("elevation_cutoff", self.elevation_cutoff)?;
+        dict.set_item("observables", &self.observables)?;
+        dict.set_item("num_stations", self.num_stations)?;
+        dict.set_item("num_satellites", self.num_satellites)?;
+        dict.set_item("base_radius", self.base_radius)?;
+        dict.set_item("map_dimension", self.map_dimension)?;
+        dict.set_item("heights", self.heights)?;
+        dict.set_item("latitudes", self.latitudes)?;
+        dict.set_item("longitudes", self.longitudes)?;
+        dict.set_item("exponent", self.exponent)?;
+        dict.set_item("comments", &self.comments)?;
+        Ok(dict)
+    }
+}
+
+// "PRN / BIAS / RMS" (3X,A3,2F10.3) and "STATION / BIAS / RMS"
+// (3X,A1,2X,A4,1X,A9,6X,2F10.3) auxiliary records
+fn parse_dcb(line: &str, label: &str) -> Option<Dcb> {
+    let values: Vec<f64> = match label {
+        "PRN / BIAS / RMS" => line.get(6..60)?,
+        _ => line.get(20..60)?,
+    }
+    .split_whitespace()
+    .filter_map(|v| v.parse().ok())
+    .collect();
+
+    match label {
+        "PRN / BIAS / RMS" => Some(Dcb {
+            kind: "satellite",
+            system: field(line, 3, 4),
+            name: field(line, 3, 6)?,
+            domes: None,
+            bias: values.first().copied(),
+            rms: values.get(1).copied(),
+        }),
+        "STATION / BIAS / RMS" => Some(Dcb {
+            kind: "station",
+            system: field(line, 3, 4),
+            name: field(line, 6, 10)?,
+            domes: field(line, 11, 20),
+            bias: values.first().copied(),
+            rms: values.get(1).copied(),
+        }),
+        _ => None,
+    }
+}
+
+// Header records and auxiliary DCBs, read from the header lines: the `rinex`
+// crate parses the maps but exposes neither the auxiliary data block nor
+// several header records (description, written epochs, grid definitions)
+fn parse_ionex_header<R: BufRead>(reader: R) -> Result<(IonexHeader, Vec<Dcb>), RinexError> {
+    let mut header = IonexHeader {
+        exponent: -1,
+        ..Default::default()
+    };
+    let mut dcbs = Vec::new();
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.map_err(|e| RinexError::parse(format!("IONEX read error: {}", e)))?;
+
+        if index == 0 && line.get(20..21) != Some("I") {
+            return Err(RinexError::wrong_type("This is not an IONEX file"));
+        }
+        match line.get(60..).unwrap_or("").trim() {
+            "END OF HEADER" => return Ok((header, dcbs)),
+            label @ ("PRN / BIAS / RMS" | "STATION / BIAS / RMS") => dcbs.extend(parse_dcb(&line, label)),
+            label => header.parse_line(&line, label),
+        }
+    }
+
+    Err(RinexError::parse("IONEX header is missing END OF HEADER"))
+}
+
+// One row per grid point of each map, in the file order (latitude bands of
+// each height, then longitudes), so that missing values stay as nulls
+fn grid_frame(rinex: &Rinex, header: &IonexHeader) -> Result<DataFrame, RinexError> {
+    let (Some(heights), Some(lats), Some(lons)) = (
+        grid_axis(header.heights),
+        grid_axis(header.latitudes),
+        grid_axis(header.longitudes),
+    ) else {
+        return Err(RinexError::parse("IONEX header has an invalid grid definition"));
+    };
+
+    let mut epochs = BTreeSet::new();
+    let mut values = HashMap::new();
+    for (key, tec) in rinex.record.as_ionex().into_iter().flatten() {
+        let epoch = epoch_to_nanos(&key.epoch, TimeScale::UTC);
+        let coordinates = &key.coordinates;
+        epochs.insert(epoch);
+        values.insert(
+            grid_key(
+                epoch,
+                coordinates.latitude_ddeg(),
+                coordinates.longitude_ddeg(),
+                coordinates.altitude_km(),
+            ),
+            (tec.tecu(), tec.rms_tecu()),
+        );
+    }
+
+    let size = epochs.len() * heights.len() * lats.len() * lons.len();
+    let mut columns: [Vec<f64>; 3] = Default::default();
+    let mut epoch_column = Vec::with_capacity(size);
+    let mut tec_column = Vec::with_capacity(size);
+    let mut rms_column = Vec::with_capacity(size);
+    for &epoch in &epochs {
+        for &height in &heights {
+            for &lat in &lats {
+                for &lon in &lons {
+                    let value = values.get(&grid_key(epoch, lat, lon, height));
+                    epoch_column.push(epoch);
+                    columns[0].push(lat);
+                    columns[1].push(lon);
+                    columns[2].push(height);
+                    tec_column.push(value.map(|v| v.0));
+                    rms_column.push(value.and_then(|v| v.1));
+                }
+            }
+        }
+    }
+
+    let [lat, lon, height] = columns;
+    let mut df = df![
+        "lat" => lat,
+        "lon" => lon,
+        "height" => height,
+        "tec" => tec_column,
+        "rms" => rms_column,
+    ]
+    .map_err(|e| RinexError::parse(format!("IONEX grid error: {}", e)))?;
+    df.insert_column(0, datetime_series("epoch", epoch_column))
+        .map_err(|e| RinexError::parse(format!("IONEX grid error: {}", e)))?;
+
+    Ok(df)
+}
+
+fn dcb_frame(dcbs: &[Dcb]) -> PyResult<DataFrame> {
+    df![
+        "kind" => dcbs.iter().map(|d| d.kind).collect::<Vec<_>>(),
+        "system" => dcbs.iter().map(|d| d.system.as_deref()).collect::<Vec<_>>(),
+        "name" => dcbs.iter().map(|d| d.name.as_str()).collect::<Vec<_>>(),
+        "domes" => dcbs.iter().map(|d| d.domes.as_deref()).collect::<Vec<_>>(),
+        "bias" => dcbs.iter().map(|d| d.bias).collect::<Vec<_>>(),
+        "rms" => dcbs.iter().map(|d| d.rms).collect::<Vec<_>>(),
+    ]
+    .map_err(polars_error)
+}
+
+// Global (or regional) ionosphere maps: one row per (epoch, lat, lon, height)
+// with TEC and RMS in TECU (exponents applied, 9999 read as missing), the
+// satellite and station DCBs (ns) of the auxiliary data, and the header
+#[pyfunction]
+pub(crate) fn read_ionex<'py>(
+    py: Python<'py>,
+    path: &str,
+) -> PyResult<(PyDataFrame, PyDataFrame, Bound<'py, PyDict>)> {
+    let path = Path::new(path);
+    check_exists(path)?;
+
+    let reader = open_text_reader(path)
+        .map_err(|e| RinexError::parse(format!("IONEX read error: {}", e)).with_path(path))?;
+    let (header, dcbs) = parse_ionex_header(reader).map_err(|e| e.with_path(path))?;
+
+    let rinex = Rinex::from_file(path)
+        .map_err(|e| RinexError::parse(format!("IONEX parsing error: {}", e)).with_path(path))?;
+    if !rinex.is_ionex() {
+        return Err(RinexError::wrong_type("This is not an IONEX file")
+            .with_path(path)
+            .into());
+    }
+    let grid = grid_frame(&rinex, &header).map_err(|e| e.with_path(path))?;
+
+    Ok((PyDataFrame(grid), PyDataFrame(dcb_frame(&dcbs)?), header.to_dict(py)?))
+}
+
+const MONTHS: [&str; 12] = ["JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC"];
+
+fn value_error(message: String) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyValueError, _>(message)
+}
+
+// Header record: content in columns 1-60, label in columns 61-80
+fn record(content: &str, label: &str) -> String {
+    let content: String = content.chars().take(60).collect();
+    format!("{:<60}{:<20}\n", content, label)
+}
+
+fn epoch_record(nanos: i64, label: &str) -> String {
+    let (y, m, d, hh, mm, ss) = nanos_to_calendar(nanos);
+    record(&format!("{:6}{:6}{:6}{:6}{:6}{:6}", y, m, d, hh, mm, ss), label)
+}
+
+// Value of the `header` dict of `write_ionex`, None when missing
+fn header_item<'py, T: FromPyObject<'py>>(header: Option<&Bound<'py, PyDict>>, key: &str) -> PyResult<Option<T>> {
+    match header.map(|h| h.get_item(key)).transpose()?.flatten() {
+        Some(value) if !value.is_none() => Ok(Some(value.extract()?)),
+        _ => Ok(None),
+    }
+}
+
+// Grid to be written: axes in tenths of a degree (or km), values by `grid_key`
+struct OutputGrid {
+    epochs: Vec<i64>,
+    heights: Vec<i64>,
+    lats: Vec<i64>,
+    lons: Vec<i64>,
+    tec: HashMap<(i64, i64, i64, i64), f64>,
+    rms: HashMap<(i64, i64, i64, i64), f64>,
+}
+
+// Step of an evenly spaced axis, 0 when it has a single value
+fn axis_step(values: &[i64], name: &str) -> PyResult<i64> {
+    let step = values.get(1).map_or(0, |v| v - values[0]);
+    if values.windows(2).any(|w| w[1] - w[0] != step) {
+        return Err(value_error(format!("Grid {} are not evenly spaced", name)));
+    }
+    Ok(step)
+}
+
+// Smallest exponent such that the largest value still fits in I5 (9999 is
+// reserved for missing values), -1 for empty or all zero maps
+fn auto_exponent(values: &[f64]) -> i32 {
+    let max = values.iter().fold(0f64, |m, v| m.max(v.abs()));
+    if max == 0.0 || !max.is_finite() {
+        return -1;
+    }
+    let mut exponent = (max / 9998.0).log10().ceil() as i32;
+    while (max * 10f64.powi(-exponent)).round() > 9998.0 {
+        exponent += 1;
+    }
+    exponent
+}
+
+fn output_grid(grid: &DataFrame, default_height: f64) -> PyResult<OutputGrid> {
+    if grid.height() == 0 {
+        return Err(value_error("Grid is empty".to_string()));
+    }
+    for name in ["lat", "lon", "tec"] {
+        if grid.column(name).is_err() {
+            return Err(value_error(format!("Missing column '{}'", name)));
+        }
+    }
+    let epochs = datetime_column(grid, "epoch")?;
+    let lats = opt_f64_column(grid, "lat")?;
+    let lons = opt_f64_column(grid, "lon")?;
+    let heights = opt_f64_column(grid, "height")?;
+    let tec = opt_f64_column(grid, "tec")?;
+    let rms = opt_f64_column(grid, "rms")?;
+
+    let mut axes: [BTreeSet<i64>; 4] = Default::default();
+    let mut keys = HashSet::new();
+    let mut tec_values = HashMap::new();
+    let mut rms_values = HashMap::new();
+    for (i, epoch) in epochs.into_iter().enumerate() {
+        let (Some(epoch), Some(lat), Some(lon)) = (epoch, lats[i], lons[i]) else {
+            return Err(value_error("Grid has missing epoch, lat or lon values".to_string()));
+        };
+        // IONEX epochs have whole seconds
+        let epoch = epoch.div_euclid(1_000_000_000) * 1_000_000_000;
+        let height = heights[i].unwrap_or(default_height);
+        let key = grid_key(epoch, lat, lon, height);
+        if !keys.insert(key) {
+            let (y, m, d, hh, mm, ss) = nanos_to_calendar(epoch);
+            return Err(value_error(format!(
+                "Grid has several rows for epoch {:04}-{:02}-{:02}T{:02}:{:02}:{:02} (to the second), lat {}, lon {}, height {}",
+                y, m, d, hh, mm, ss, lat, lon, height
+            )));
+        }
+        axes[0].insert(key.0);
+        axes[1].insert(key.1);
+        axes[2].insert(key.2);
+        axes[3].insert(key.3);
+        // NaN means no data, like null: both are written as 9999
+        if let Some(value) = tec[i].filter(|v| !v.is_nan()) {
+            tec_values.insert(key, value);
+        }
+        if let Some(value) = rms[i].filter(|v| !v.is_nan()) {
+            rms_values.insert(key, value);
+        }
+    }
+
+    let [epochs, lats, lons, heights] = axes.map(|axis| axis.into_iter().collect::<Vec<_>>());
+    Ok(OutputGrid {
+        epochs,
+        heights,
+        // Latitude bands go from north to south
+        lats: lats.into_iter().rev().collect(),
+        lons,
+        tec: tec_values,
+        rms: rms_values,
+    })
+}
+
+// One TEC or RMS map: epoch, exponent (when it differs from the header one),
+// then a "LAT/LON1/LON2/DLON/H" record and 16I5 values per latitude band
+fn write_map(
+    out: &mut String,
+    kind: &str,
+    index: usize,
+    epoch: i64,
+    grid: &OutputGrid,
+    header_exponent: i32,
+    exponent: Option<i32>,
+) -> PyResult<()> {
+    let values = if kind == "TEC" { &grid.tec } else { &grid.rms };
+    let cells: Vec<Option<f64>> = grid
+        .heights
+        .iter()
+        .flat_map(|h| grid.lats.iter().flat_map(move |lat| grid.lons.iter().map(move |lon| (*h, *lat, *lon))))
+        .map(|(h, lat, lon)| values.get(&(epoch, lat, lon, h)).copied())
+        .collect();
+    let exponent = exponent.unwrap_or_else(|| auto_exponent(&cells.iter().flatten().copied().collect::<Vec<_>>()));
+    let scale = 10f64.powi(-exponent);
+
+    out.push_str(&record(&format!("{:6}", index), &format!("START OF {} MAP", kind)));
+    out.push_str(&epoch_record(epoch, "EPOCH OF CURRENT MAP"));
+    if exponent != header_exponent {
+        out.push_str(&record(&format!("{:6}", exponent), "EXPONENT"));
+    }
+
+    let tenth = |v: i64| v as f64 / 10.0;
+    let (lon1, lon2) = (grid.lons[0], grid.lons[grid.lons.len() - 1]);
+    let dlon = axis_step(&grid.lons, "longitudes")?;
+    let mut bands = cells.chunks(grid.lons.len());
+    for h in &grid.heights {
+        for lat in &grid.lats {
+            let band = bands.next().unwrap_or_default();
+            out.push_str(&record(
+                &format!(
+                    "  {:6.1}{:6.1}{:6.1}{:6.1}{:6.1}",
+                    tenth(*lat),
+                    tenth(lon1),
+                    tenth(lon2),
+                    tenth(dlon),
+                    tenth(*h)
+                ),
+                "LAT/LON1/LON2/DLON/H",
+            ));
+            for line in band.chunks(16) {
+                for value in line {
+                    let scaled = match value {
+                        Some(v) => {
+                            let scaled = (v * scale).round();
+                            if scaled.abs() > 9998.0 || !scaled.is_finite() {
+                                return Err(value_error(format!(
+                                    "{} value {} does not fit in the IONEX format with exponent {}",
+                                    kind, v, exponent
+                                )));
+                            }
+                            scaled as i32
+                        },
+                        None => MISSING,
+                    };
+                    out.push_str(&format!("{:5}", scaled));
+                }
+                out.push('\n');
+            }
+        }
+    }
+
+    out.push_str(&record(&format!("{:6}", index), &format!("END OF {} MAP", kind)));
+    Ok(())
+}
+
+// "PRN / BIAS / RMS" and "STATION / BIAS / RMS" records of a DCB frame shaped
+// like the one returned by `read_ionex`, satellites first
+fn dcb_records(dcbs: &DataFrame) -> PyResult<Vec<String>> {
+    let optional = |name: &str| -> PyResult<Vec<Option<String>>> {
+        match dcbs.column(name) {
+            Ok(_) => str_column(dcbs, name),
+            Err(_) => Ok(vec![None; dcbs.height()]),
+        }
+    };
+    let kinds = str_column(dcbs, "kind")?;
+    let names = str_column(dcbs, "name")?;
+    let systems = optional("system")?;
+    let domes = optional("domes")?;
+    let biases = opt_f64_column(dcbs, "bias")?;
+    let rms = opt_f64_column(dcbs, "rms")?;
+
+    let mut satellites = Vec::new();
+    let mut stations = Vec::new();
+    for (i, name) in names.iter().enumerate() {
+        let (Some(name), Some(bias)) = (name.as_deref(), biases[i]) else {
+            continue;
+        };
+        let rms = rms[i].map(|r| format!("{:10.3}", r)).unwrap_or_default();
+        match kinds[i].as_deref() {
+            Some("satellite") => satellites.push(record(
+                &format!("   {:<3.3}{:10.3}{}", name, bias, rms),
+                "PRN / BIAS / RMS",
+            )),
+            Some("station") => stations.push(record(
+                &format!(
+                    "   {:<1.1}  {:<4.4} {:<9.9}      {:10.3}{}",
+                    systems[i].as_deref().unwrap_or(""),
+                    name,
+                    domes[i].as_deref().unwrap_or(""),
+                    bias,
+                    rms
+                ),
+                "STATION / BIAS / RMS",
+            )),
+            other => {
+                return Err(value_error(format!(
+                    "Invalid DCB kind {:?} (expected 'satellite' or 'station')",
+                    other.unwrap_or("")
+                )));
+            },
+        }
+    }
+    satellites.extend(stations);
+    Ok(satellites)
+}
+
+fn format_ionex(
+    grid: &OutputGrid,
+    header: Option<&Bound<'_, PyDict>>,
+    dcbs: Option<&DataFrame>,
+    version: &str,
+    exponent: Option<i32>,
+) -> PyResult<String> {
+    let dlat = axis_step(&grid.lats, "latitudes")?;
+    let dlon = axis_step(&grid.lons, "longitudes")?;
+    let dhgt = axis_step(&grid.heights, "heights")?;
+    let interval = axis_step(&grid.epochs, "epochs").map_or(0, |step| step / 1_000_000_000);
+    let tenth = |v: i64| v as f64 / 10.0;
+    let definition = |values: &[i64], step: i64| {
+        format!("  {:6.1}{:6.1}{:6.1}", tenth(values[0]), tenth(values[values.len() - 1]), tenth(step))
+    };
+
+    let header_exponent = exponent.unwrap_or_else(|| auto_exponent(&grid.tec.values().copied().collect::<Vec<_>>()));
+    let date = match header_item::<String>(header, "date")? {
+        Some(date) => date,
+        None => {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |d| d.as_nanos() as i64);
+            let (y, m, d, hh, mm, _) = nanos_to_calendar(now);
+            format!("{:02}-{}-{:02} {:02}:{:02}", d, MONTHS[m as usize - 1], y % 100, hh, mm)
+        },
+    };
+
+    let mut out = String::new();
+    out.push_str(&record(
+        &format!(
+            "{:>8}{:12}{:<20}{}",
+            version,
+            "",
+            "IONOSPHERE MAPS",
+            header_item::<String>(header, "system")?.unwrap_or_else(|| "GPS".to_string())
+        ),
+        "IONEX VERSION / TYPE",
+    ));
+    out.push_str(&record(
+        &format!(
+            "{:<20}{:<20}{}",
+            header_item::<String>(header, "program")?.unwrap_or_else(|| "pytecggrs".to_string()),
+            header_item::<String>(header, "run_by")?.unwrap_or_default(),
+            date
+        ),
+        "PGM / RUN BY / DATE",
+    ));
+    for line in header_item::<Vec<String>>(header, "description")?.unwrap_or_default() {
+        out.push_str(&record(&line, "DESCRIPTION"));
+    }
+    out.push_str(&epoch_record(grid.epochs[0], "EPOCH OF FIRST MAP"));
+    out.push_str(&epoch_record(grid.epochs[grid.epochs.len() - 1], "EPOCH OF LAST MAP"));
+    out.push_str(&record(&format!("{:6}", interval), "INTERVAL"));
+    out.push_str(&record(&format!("{:6}", grid.epochs.len()), "# OF MAPS IN FILE"));
+    out.push_str(&record(
+        &format!("  {}", header_item::<String>(header, "mapping_function")?.unwrap_or_else(|| "NONE".to_string())),
+        "MAPPING FUNCTION",
+    ));
+    out.push_str(&record(
+        &format!("{:8.1}", header_item::<f64>(header, "elevation_cutoff")?.unwrap_or(0.0)),
+        "ELEVATION CUTOFF",
+    ));
+    out.push_str(&record(
+        &header_item::<String>(header, "observables")?.unwrap_or_default(),
+        "OBSERVABLES USED",
+    ));
+    if let Some(count) = header_item::<i32>(header, "num_stations")? {
+        out.push_str(&record(&format!("{:6}", count), "# OF STATIONS"));
+    }
+    if let Some(count) = header_item::<i32>(header, "num_satellites")? {
+        out.push_str(&record(&format!("{:6}", count), "# OF SATELLITES"));
+    }
+    out.push_str(&record(
+        &format!("{:8.1}", header_item::<f64>(header, "base_radius")?.unwrap_or(6371.0)),
+        "BASE RADIUS",
+    ));
+    out.push_str(&record(&format!("{:6}", if grid.heights.len() > 1 { 3 } else { 2 }), "MAP DIMENSION"));
+    out.push_str(&record(&definition(&grid.heights, dhgt), "HGT1 / HGT2 / DHGT"));
+    out.push_str(&record(&definition(&grid.lats, dlat), "LAT1 / LAT2 / DLAT"));
+    out.push_str(&record(&definition(&grid.lons, dlon), "LON1 / LON2 / DLON"));
+    out.push_str(&record(&format!("{:6}", header_exponent), "EXPONENT"));
+    for line in header_item::<Vec<String>>(header, "comments")?.unwrap_or_default() {
+        out.push_str(&record(&line, "COMMENT"));
+    }
+
+    let aux = match dcbs {
+        Some(dcbs) => dcb_records(dcbs)?,
+        None => Vec::new(),
+    };
+    if !aux.is_empty() {
+        out.push_str(&record("DIFFERENTIAL CODE BIASES", "START OF AUX DATA"));
+        aux.iter().for_each(|line| out.push_str(line));
+        out.push_str(&record("DIFFERENTIAL CODE BIASES", "END OF AUX DATA"));
+    }
+    out.push_str(&record("", "END OF HEADER"));
+
+    for (i, epoch) in grid.epochs.iter().enumerate() {
+        write_map(&mut out, "TEC", i + 1, *epoch, grid, header_exponent, exponent)?;
+    }
+    if !grid.rms.is_empty() {
+        for (i, epoch) in grid.epochs.iter().enumerate() {
+            write_map(&mut out, "RMS", i + 1, *epoch, grid, header_exponent, exponent)?;
+        }
+    }
+    out.push_str(&record("", "END OF FILE"));
+    Ok(out)
+}
+
+// Writes an IONEX 1.0/1.1 file (gzip-compressed when `path` ends with .gz)
+// out of a regular grid with columns epoch, lat, lon, tec and optionally
+// height and rms (TECU), one map per epoch. `header` takes the keys of the
+// dict returned by `read_ionex` (system, program, run_by, date, description,
+// mapping_function, elevation_cutoff, observables, num_stations,
+// num_satellites, base_radius, comments); grid definitions, epochs and
+// number of maps come from the grid. `dcbs` is written as auxiliary data.
+// Exponents are chosen per map to keep the most digits, unless `exponent` is
+// given.
+#[pyfunction]
+#[pyo3(signature = (path, grid, header=None, dcbs=None, version="1.1", exponent=None))]
+pub(crate) fn write_ionex(
+    path: &str,
+    grid: PyDataFrame,
+    header: Option<&Bound<'_, PyDict>>,
+    dcbs: Option<PyDataFrame>,
+    version: &str,
+    exponent: Option<i32>,
+) -> PyResult<()> {
+    if !matches!(version, "1.0" | "1.1") {
+        return Err(value_error(format!("Unsupported IONEX version: {} (expected 1.0 or 1.1)", version)));
+    }
+
+    let default_height = header_item::<Vec<Option<f64>>>(header, "heights")?
+        .and_then(|heights| heights.first().copied().flatten())
+        .unwrap_or(450.0);
+    let grid = output_grid(&grid.0, default_height)?;
+    let text = format_ionex(&grid, header, dcbs.as_ref().map(|d| &d.0), version, exponent)?;
+
+    if path.ends_with(".gz") {
+        let mut encoder = GzEncoder::new(File::create(path)?, Compression::default());
+        encoder.write_all(text.as_bytes())?;
+        encoder.finish()?;
+    } else {
+        std::fs::write(path, text)?;
+    }
+    Ok(())
+}
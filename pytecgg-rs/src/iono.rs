@@ -0,0 +1,190 @@
+use polars::prelude::*;
+use pyo3::prelude::*;
+use pyo3_polars::PyDataFrame;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::error::polars_error;
+use crate::header::{field, parse_f64, parse_i32};
+use crate::scan::NavText;
+use crate::time::datetime_series_opt;
+
+const KLOBUCHAR: [&str; 9] = [
+    "alpha0", "alpha1", "alpha2", "alpha3", "beta0", "beta1", "beta2", "beta3", "region_code",
+];
+const NEQUICK_G: [&str; 4] = ["ai0", "ai1", "ai2", "idf"];
+const BDGIM: [&str; 9] = [
+    "alpha1", "alpha2", "alpha3", "alpha4", "alpha5", "alpha6", "alpha7", "alpha8", "alpha9",
+];
+
+// One set of broadcast coefficients, from a header record or a RINEX 4 ION message
+struct IonoRecord {
+    source: &'static str,
+    constellation: String,
+    sv: Option<String>,
+    msg_type: Option<String>,
+    epoch: Option<i64>,
+    time_system: Option<String>,
+    time_mark: Option<String>,
+    values: Vec<Option<f64>>,
+}
+
+// Constellation name (as in `read_rinex_nav`) and time system of an SV letter
+pub(crate) fn constellation_of(letter: char) -> (&'static str, &'static str) {
+    match letter {
+        'G' => ("GPS", "GPST"),
+        'R' => ("GLONASS", "UTC"),
+        'E' => ("Galileo", "GST"),
+        'C' => ("BeiDou", "BDT"),
+        'J' => ("QZSS", "QZSST"),
+        'I' => ("IRNSS", "IRNWT"),
+        'S' => ("SBAS", "GPST"),
+        _ => ("Unknown", "GPST"),
+    }
+}
+
+// Header records: RINEX 3 "IONOSPHERIC CORR", RINEX 2 "ION ALPHA" / "ION BETA"
+fn header_records(nav: &NavText) -> (Vec<IonoRecord>, Vec<IonoRecord>) {
+    let mut klobuchar: BTreeMap<(String, Option<String>, Option<String>), [Option<f64>; 8]> =
+        BTreeMap::new();
+    let mut nequick = Vec::new();
+
+    for line in nav.records("IONOSPHERIC CORR") {
+        let Some(kind) = field(line, 0, 4) else {
+            continue;
+        };
+        let values: Vec<Option<f64>> = (0..4)
+            .map(|k| parse_f64(line, 5 + 12 * k, 17 + 12 * k))
+            .collect();
+        // BDS coefficients may be given per hour (time mark A-X) and broadcasting SV
+        let time_mark = field(line, 54, 55);
+        let prn = parse_i32(line, 56, 58);
+
+        let (constellation, letter) = match kind.get(..3).unwrap_or("") {
+            "GPS" => ("GPS", 'G'),
+            "QZS" => ("QZSS", 'J'),
+            "BDS" => ("BeiDou", 'C'),
+            "IRN" => ("IRNSS", 'I'),
+            "GAL" => ("Galileo", 'E'),
+            _ => continue,
+        };
+        let sv = prn.map(|prn| format!("{}{:02}", letter, prn));
+
+        if constellation == "Galileo" {
+            nequick.push(IonoRecord {
+                source: "header",
+                constellation: constellation.to_string(),
+                sv,
+                msg_type: None,
+                epoch: None,
+                time_system: None,
+                time_mark,
+                values: values[..3].to_vec(),
+            });
+            continue;
+        }
+
+        let offset = if kind.ends_with('B') { 4 } else { 0 };
+        let entry = klobuchar
+            .entry((constellation.to_string(), time_mark, sv))
+            .or_insert([None; 8]);
+        entry[offset..offset + 4].copy_from_slice(&values);
+    }
+
+    for (label, offset) in [("ION ALPHA", 0), ("ION BETA", 4)] {
+        for line in nav.records(label) {
+            let entry = klobuchar
+                .entry(("GPS".to_string(), None, None))
+                .or_insert([None; 8]);
+            for k in 0..4 {
+                entry[offset + k] = parse_f64(line, 2 + 12 * k, 14 + 12 * k);
+            }
+        }
+    }
+
+    let klobuchar = klobuchar
+        .into_iter()
+        .map(|((constellation, time_mark, sv), values)| IonoRecord {
+            source: "header",
+            constellation,
+            sv,
+            msg_type: None,
+            epoch: None,
+            time_system: None,
+            time_mark,
+            values: values.to_vec(),
+        })
+        .collect();
+
+    (klobuchar, nequick)
+}
+
+fn frame(records: &[IonoRecord], columns: &[&str]) -> PyResult<DataFrame> {
+    let source: Vec<&str> = records.iter().map(|r| r.source).collect();
+    let constellation: Vec<&str> = records.iter().map(|r| r.constellation.as_str()).collect();
+    let sv: Vec<Option<&str>> = records.iter().map(|r| r.sv.as_deref()).collect();
+    let msg_type: Vec<Option<&str>> = records.iter().map(|r| r.msg_type.as_deref()).collect();
+    let time_system: Vec<Option<&str>> = records.iter().map(|r| r.time_system.as_deref()).collect();
+    let time_mark: Vec<Option<&str>> = records.iter().map(|r| r.time_mark.as_deref()).collect();
+
+    let mut df = df![
+        "source" => source,
+        "constellation" => constellation,
+        "sv" => sv,
+        "msg_type" => msg_type,
+        "time_system" => time_system,
+        "time_mark" => time_mark,
+    ]
+    .map_err(polars_error)?;
+
+    // Transmission time of RINEX 4 messages, in the scale given by `time_system`
+    let epochs = records.iter().map(|r| r.epoch).collect();
+    df.insert_column(4, datetime_series_opt("epoch", epochs))
+        .map_err(polars_error)?;
+
+    for (i, name) in columns.iter().enumerate() {
+        let values = records.iter().map(|r| r.values.get(i).copied().flatten());
+        let series = Float64Chunked::from_iter_options((*name).into(), values).into_series();
+        df.with_column(series)
+            .map_err(polars_error)?;
+    }
+
+    Ok(df)
+}
+
+// Broadcast ionospheric model coefficients of a navigation file, one frame
+// per model: "klobuchar" (GPS, QZSS, BDS D1/D2, IRNSS), "nequick_g" (Galileo)
+// and "bdgim" (BDS-3 CNAV)
+#[pyfunction]
+pub(crate) fn read_rinex_nav_iono(path: &str) -> PyResult<BTreeMap<String, PyDataFrame>> {
+    let nav = NavText::from_path(Path::new(path))?;
+
+    let (mut klobuchar, mut nequick) = header_records(&nav);
+    let mut bdgim = Vec::new();
+
+    for message in nav.messages("ION") {
+        let letter = message.sv.chars().next().unwrap_or(' ');
+        let (constellation, time_system) = constellation_of(letter);
+        let record = IonoRecord {
+            source: "message",
+            constellation: constellation.to_string(),
+            sv: Some(message.sv.clone()),
+            msg_type: Some(message.msg_type.clone()),
+            epoch: message.epoch_nanos(),
+            time_system: Some(time_system.to_string()),
+            time_mark: None,
+            values: message.values(),
+        };
+        match letter {
+            'E' => nequick.push(record),
+            'C' if message.msg_type.starts_with("CNV") => bdgim.push(record),
+            _ => klobuchar.push(record),
+        }
+    }
+
+    let mut result = BTreeMap::new();
+    result.insert("klobuchar".to_string(), PyDataFrame(frame(&klobuchar, &KLOBUCHAR)?));
+    result.insert("nequick_g".to_string(), PyDataFrame(frame(&nequick, &NEQUICK_G)?));
+    result.insert("bdgim".to_string(), PyDataFrame(frame(&bdgim, &BDGIM)?));
+    Ok(result)
+}
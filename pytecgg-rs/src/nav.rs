@@ -0,0 +1,253 @@
+use pyo3::prelude::*;
+use rinex::navigation::NavKey;
+use rinex::prelude::*;
+use polars::prelude::*;
+use pyo3_polars::PyDataFrame;
+use std::path::Path;
+use std::collections::{BTreeSet, BTreeMap};
+
+use crate::buffer::{read_buffer, rinex_from_bytes};
+use crate::error::{polars_error, RinexError};
+use crate::obs::check_exists;
+use crate::time::{datetime_series, epoch_to_nanos};
+
+pub(crate) fn load_nav_rinex(path: &Path) -> PyResult<Rinex> {
+    check_exists(path)?;
+    Ok(parse_nav_file(path)?)
+}
+
+pub(crate) fn parse_nav_file(path: &Path) -> Result<Rinex, RinexError> {
+    let rinex = Rinex::from_file(path)
+        .map_err(|e| RinexError::parse(format!("RINEX parsing error: {}", e)).with_path(path))?;
+
+    ensure_navigation(rinex).map_err(|e| e.with_path(path))
+}
+
+fn ensure_navigation(rinex: Rinex) -> Result<Rinex, RinexError> {
+    if !rinex.is_navigation_rinex() {
+        return Err(RinexError::wrong_type("This is not a RINEX Navigation file"));
+    }
+
+    Ok(rinex)
+}
+
+const MSG_TYPES: [&str; 11] = [
+    "LNAV", "CNAV", "CNV1", "CNV2", "CNV3", "INAV", "FNAV", "D1", "D2", "FDMA", "SBAS",
+];
+
+// Requested navigation message types, upper-cased and validated
+pub(crate) fn parse_msg_types(msg_types: Option<Vec<String>>) -> PyResult<Option<BTreeSet<String>>> {
+    msg_types
+        .map(|names| {
+            names
+                .into_iter()
+                .map(|name| {
+                    let name = name.trim().to_uppercase();
+                    if MSG_TYPES.contains(&name.as_str()) {
+                        Ok(name)
+                    } else {
+                        Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                            "Invalid navigation message type '{}', expected one of {}",
+                            name,
+                            MSG_TYPES.join(", ")
+                        )))
+                    }
+                })
+                .collect()
+        })
+        .transpose()
+}
+
+// Navigation message type of a record. RINEX 4 states it explicitly; for
+// older revisions it is inferred from the Galileo data sources (I/NAV vs
+// F/NAV) and from the BeiDou PRN (D2 is broadcast by GEO satellites).
+fn msg_type_name(nav_key: &NavKey, params: &BTreeMap<String, f64>, legacy: bool) -> String {
+    if !legacy {
+        return nav_key.msgtype.to_string().to_uppercase();
+    }
+    let prn = nav_key.sv.prn;
+    match nav_key.sv.constellation {
+        Constellation::Galileo => match params.get("dataSrc").map(|src| *src as u32) {
+            Some(src) if src & 0x02 != 0 => "FNAV",
+            _ => "INAV",
+        },
+        Constellation::BeiDou if prn <= 5 || prn >= 59 => "D2",
+        Constellation::BeiDou => "D1",
+        Constellation::Glonass => "FDMA",
+        c if c.is_sbas() => "SBAS",
+        _ => "LNAV",
+    }
+    .to_string()
+}
+
+#[pyfunction]
+#[pyo3(signature = (path, msg_types=None))]
+pub(crate) fn read_rinex_nav(
+    path: &str,
+    msg_types: Option<Vec<String>>,
+) -> PyResult<BTreeMap<String, PyDataFrame>> {
+    let msg_types = parse_msg_types(msg_types)?;
+    let rinex = load_nav_rinex(Path::new(path))?;
+    nav_frames(&rinex, msg_types.as_ref())
+}
+
+// Same as `read_rinex_nav`, for content already in memory: `bytes`, any
+// buffer-protocol object, or a binary file-like object (gzip is detected)
+#[pyfunction]
+#[pyo3(signature = (data, msg_types=None))]
+pub(crate) fn read_rinex_nav_bytes(
+    data: &Bound<'_, PyAny>,
+    msg_types: Option<Vec<String>>,
+) -> PyResult<BTreeMap<String, PyDataFrame>> {
+    let msg_types = parse_msg_types(msg_types)?;
+    let content = read_buffer(data)?;
+    let rinex = ensure_navigation(rinex_from_bytes(&content)?)?;
+    nav_frames(&rinex, msg_types.as_ref())
+}
+
+fn nav_frames(
+    rinex: &Rinex,
+    msg_types: Option<&BTreeSet<String>>,
+) -> PyResult<BTreeMap<String, PyDataFrame>> {
+    records_frames(nav_records(rinex, msg_types, 0), None)
+}
+
+// Un record di effemeridi, prima della costruzione dei DataFrame
+pub(crate) struct NavRecord {
+    pub constellation: &'static str,
+    pub sv: String,
+    pub toc_ns: i64,
+    pub time_system: String,
+    pub msg_type: String,
+    pub params: BTreeMap<String, f64>,
+    // Indice del file di provenienza (lettura di più file)
+    pub source: usize,
+}
+
+pub(crate) fn nav_records(
+    rinex: &Rinex,
+    msg_types: Option<&BTreeSet<String>>,
+    source: usize,
+) -> Vec<NavRecord> {
+    let legacy = rinex.header.version.major < 4;
+    let mut records = Vec::new();
+
+    for (nav_key, ephemeris) in rinex.nav_ephemeris_frames_iter() {
+        let constellation = match nav_key.sv.constellation {
+            Constellation::GPS => "GPS",
+            Constellation::Glonass => "GLONASS",
+            Constellation::Galileo => "Galileo",
+            Constellation::BeiDou => "BeiDou",
+            Constellation::QZSS => "QZSS",
+            Constellation::IRNSS => "IRNSS",
+            Constellation::SBAS => "SBAS",
+            _ => "Unknown",
+        };
+
+        // SV nel formato "G05", come nel lettore delle osservazioni
+        let sv = nav_key.sv.to_string();
+        // Time of clock, nella scala di tempo della costellazione
+        let toc_ns = epoch_to_nanos(&nav_key.epoch, nav_key.epoch.time_scale);
+        let time_system = nav_key.epoch.time_scale.to_string();
+
+        // Crea una mappa per tutti i parametri
+        let mut params = BTreeMap::new();
+
+        // Aggiungi parametri di clock
+        params.insert("clock_bias".to_string(), ephemeris.clock_bias);
+        params.insert("clock_drift".to_string(), ephemeris.clock_drift);
+        params.insert("clock_drift_rate".to_string(), ephemeris.clock_drift_rate);
+
+        // Aggiungi tutti i parametri orbitali disponibili
+        for (key, value) in &ephemeris.orbits {
+            params.insert(key.to_string(), value.as_f64());
+        }
+
+        // Tipo di messaggio (LNAV, CNAV, INAV, FNAV, D1, D2...), eventualmente filtrato
+        let msg_type = msg_type_name(&nav_key, &params, legacy);
+        if msg_types.is_some_and(|types| !types.contains(&msg_type)) {
+            continue;
+        }
+
+        records.push(NavRecord {
+            constellation,
+            sv,
+            toc_ns,
+            time_system,
+            msg_type,
+            params,
+            source,
+        });
+    }
+
+    records
+}
+
+// DataFrame per costellazione; con `sources`, una colonna "source" riporta
+// il file di provenienza di ogni record
+pub(crate) fn records_frames(
+    records: Vec<NavRecord>,
+    sources: Option<&[String]>,
+) -> PyResult<BTreeMap<String, PyDataFrame>> {
+    // Mappa per costellazione -> record
+    let mut by_constellation: BTreeMap<&str, Vec<NavRecord>> = BTreeMap::new();
+    for record in records {
+        by_constellation.entry(record.constellation).or_default().push(record);
+    }
+
+    // Crea i DataFrame per ogni costellazione
+    let mut result = BTreeMap::new();
+
+    for (constellation, data) in by_constellation {
+        let times: Vec<i64> = data.iter().map(|r| r.toc_ns).collect();
+        let time_systems: Vec<&str> = data.iter().map(|r| r.time_system.as_str()).collect();
+        let svs: Vec<&str> = data.iter().map(|r| r.sv.as_str()).collect();
+        let msg_types: Vec<&str> = data.iter().map(|r| r.msg_type.as_str()).collect();
+
+        // Raccogli tutti i nomi dei parametri univoci
+        let mut all_params = BTreeSet::new();
+        for record in &data {
+            for param_name in record.params.keys() {
+                all_params.insert(param_name.clone());
+            }
+        }
+
+        // Crea il DataFrame
+        let mut df_builder = df! {
+            "time_system" => time_systems,
+            "sv" => svs,
+            "msg_type" => msg_types,
+        }.map_err(polars_error)?;
+
+        // Toc come colonna Datetime (etichettata UTC, ma espressa nella scala `time_system`)
+        df_builder.insert_column(0, datetime_series("toc", times))
+            .map_err(polars_error)?;
+
+        // File di provenienza
+        if let Some(sources) = sources {
+            let paths: Vec<&str> = data.iter().map(|r| sources[r.source].as_str()).collect();
+            df_builder.with_column(Series::new("source".into(), paths))
+                .map_err(polars_error)?;
+        }
+
+        // Aggiungi tutte le colonne dei parametri
+        for param_name in all_params {
+            let values = data.iter().map(|r| r.params.get(&param_name).copied());
+            let series = Float64Chunked::from_iter_options(param_name.as_str().into(), values)
+                .into_series();
+            df_builder.with_column(series)
+                .map_err(polars_error)?;
+        }
+
+        // Imposta l'indice multi-livello (toc, sv)
+        df_builder = df_builder
+            .lazy()
+            .with_row_index("row_id", None)
+            .collect()
+            .map_err(polars_error)?;
+
+        result.insert(constellation.to_string(), PyDataFrame(df_builder));
+    }
+
+    Ok(result)
+}
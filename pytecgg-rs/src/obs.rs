@@ -0,0 +1,684 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use rinex::prelude::*;
+use polars::prelude::*;
+use pyo3_polars::PyDataFrame;
+use std::path::Path;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::io::Cursor;
+
+use crate::buffer::{read_buffer, rinex_from_bytes};
+use crate::error::{polars_error, RinexError};
+use crate::filter::ObsFilter;
+use crate::header::{obs_header_dict, open_text_reader, read_text, HeaderExtras};
+use crate::scan::{locate_corrupted, parse_lenient, scan_epoch_lines, EpochLine, SkippedEpoch};
+use crate::time::{datetime_series, datetime_series_opt, epoch_to_nanos, parse_timescale};
+
+fn ssi_value(snr: SNR) -> i32 {
+    match snr {
+        SNR::DbHz0 => 0,
+        SNR::DbHz12 => 1,
+        SNR::DbHz12_17 => 2,
+        SNR::DbHz18_23 => 3,
+        SNR::DbHz24_29 => 4,
+        SNR::DbHz30_35 => 5,
+        SNR::DbHz36_41 => 6,
+        SNR::DbHz42_47 => 7,
+        SNR::DbHz48_53 => 8,
+        SNR::DbHz54 => 9,
+    }
+}
+
+// RINEX epoch flag (0: OK, 1: power failure, 2-5: events, 6: cycle slip records)
+fn epoch_flag_value(flag: EpochFlag) -> i32 {
+    match flag {
+        EpochFlag::Ok => 0,
+        EpochFlag::PowerFailure => 1,
+        EpochFlag::AntennaBeingMoved => 2,
+        EpochFlag::NewSiteOccupation => 3,
+        EpochFlag::HeaderInformationFollows => 4,
+        EpochFlag::ExternalEvent => 5,
+        EpochFlag::CycleSlip => 6,
+    }
+}
+
+pub(crate) fn check_exists(path: &Path) -> PyResult<()> {
+    if !path.exists() {
+        return Err(PyErr::new::<pyo3::exceptions::PyFileNotFoundError, _>(
+            format!("File not found: {}", path.display())
+        ));
+    }
+    Ok(())
+}
+
+// A file the parser rejects is read again and parsed by parts, to locate
+// its corrupted epochs: in lenient mode they are dropped and reported rather
+// than failing the whole file, otherwise the first one is raised.
+pub(crate) fn parse_obs_file(path: &Path, lenient: bool) -> Result<(Rinex, Vec<SkippedEpoch>), RinexError> {
+    let (rinex, skipped) = match Rinex::from_file(path) {
+        Ok(rinex) => (rinex, Vec::new()),
+        Err(e) => {
+            let content = read_text(path).ok();
+            match content.as_deref().filter(|_| lenient).and_then(parse_lenient) {
+                Some(parsed) => parsed,
+                None => {
+                    let error = content
+                        .as_deref()
+                        .and_then(locate_corrupted)
+                        .unwrap_or_else(|| RinexError::parse(format!("RINEX parsing error: {}", e)));
+                    return Err(error.with_path(path));
+                },
+            }
+        },
+    };
+
+    let rinex = ensure_observation(rinex).map_err(|e| e.with_path(path))?;
+    Ok((rinex, skipped))
+}
+
+fn ensure_observation(rinex: Rinex) -> Result<Rinex, RinexError> {
+    if !rinex.is_observation_rinex() {
+        return Err(RinexError::wrong_type("This is not a RINEX Observation file"));
+    }
+
+    Ok(rinex)
+}
+
+// Extract approximate rx coordinates (ECEF) and RINEX version
+fn header_summary(rinex: &Rinex) -> ((f64, f64, f64), String) {
+    let position = rinex.header.rx_position.unwrap_or((f64::NAN, f64::NAN, f64::NAN));
+    (position, rinex.header.version.to_string())
+}
+
+// Output of the observation readers when any of `with_header`, `with_epochs`
+// or `lenient` is set (the plain `(df, (x, y, z), version)` tuple is returned
+// otherwise). What was not asked for is None.
+#[pyclass(module = "pytecggrs", frozen)]
+pub(crate) struct ObsResult {
+    data: PyObject,
+    rx_position: (f64, f64, f64),
+    version: String,
+    header: Option<Py<PyDict>>,
+    epochs: Option<PyObject>,
+    skipped_epochs: Option<Py<PyList>>,
+}
+
+#[pymethods]
+impl ObsResult {
+    #[getter]
+    fn data(&self, py: Python<'_>) -> PyObject {
+        self.data.clone_ref(py)
+    }
+
+    #[getter]
+    fn rx_position(&self) -> (f64, f64, f64) {
+        self.rx_position
+    }
+
+    #[getter]
+    fn version(&self) -> String {
+        self.version.clone()
+    }
+
+    #[getter]
+    fn header(&self, py: Python<'_>) -> Option<Py<PyDict>> {
+        self.header.as_ref().map(|header| header.clone_ref(py))
+    }
+
+    // Per-epoch table, event epochs included
+    #[getter]
+    fn epochs(&self, py: Python<'_>) -> Option<PyObject> {
+        self.epochs.as_ref().map(|epochs| epochs.clone_ref(py))
+    }
+
+    // Epochs dropped in lenient mode
+    #[getter]
+    fn skipped_epochs(&self, py: Python<'_>) -> Option<Py<PyList>> {
+        self.skipped_epochs.as_ref().map(|skipped| skipped.clone_ref(py))
+    }
+}
+
+fn obs_output<'py>(
+    py: Python<'py>,
+    df: DataFrame,
+    rinex: &Rinex,
+    header: Option<Bound<'py, PyDict>>,
+    epochs: Option<DataFrame>,
+    skipped: Option<&[SkippedEpoch]>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let (rx_position, version) = header_summary(rinex);
+    let data = PyDataFrame(df).into_pyobject(py)?.into_any();
+    if header.is_none() && epochs.is_none() && skipped.is_none() {
+        return Ok((data, rx_position, version).into_pyobject(py)?.into_any());
+    }
+
+    let epochs = epochs
+        .map(|epochs| PyDataFrame(epochs).into_pyobject(py).map(|epochs| epochs.into_any().unbind()))
+        .transpose()?;
+    let skipped_epochs = skipped
+        .map(|skipped| {
+            let skipped = skipped
+                .iter()
+                .map(|s| s.to_dict(py))
+                .collect::<PyResult<Vec<_>>>()?;
+            PyList::new(py, skipped).map(Bound::unbind)
+        })
+        .transpose()?;
+    let result = ObsResult {
+        data: data.unbind(),
+        rx_position,
+        version,
+        header: header.map(Bound::unbind),
+        epochs,
+        skipped_epochs,
+    };
+    Ok(Bound::new(py, result)?.into_any())
+}
+
+// Time scale the epochs of a file are expressed in
+fn file_timescale(rinex: &Rinex) -> TimeScale {
+    if let Record::ObsRecord(obs_data) = &rinex.record {
+        if let Some(obs_key) = obs_data.keys().next() {
+            return obs_key.epoch.time_scale;
+        }
+    }
+    rinex
+        .header
+        .obs
+        .as_ref()
+        .and_then(|obs| obs.timeof_first_obs)
+        .map(|t| t.time_scale)
+        .unwrap_or(TimeScale::GPST)
+}
+
+// Per-epoch table, event epochs included (they carry no signal, hence no
+// row in the observation frames): epoch flag, number of satellites or of
+// special records following an event, and receiver clock offset. Built
+// from the epoch lines of the file when available, from the record otherwise.
+pub(crate) fn obs_epochs_frame(
+    rinex: &Rinex,
+    lines: Option<&[EpochLine]>,
+    timescale: Option<TimeScale>,
+    filter: &ObsFilter,
+) -> PyResult<DataFrame> {
+    let mut epochs = Vec::new();
+    let mut time_systems = Vec::new();
+    let mut epoch_flags = Vec::new();
+    let mut num_svs = Vec::new();
+    let mut event_lines = Vec::new();
+    let mut clock_offsets = Vec::new();
+
+    match (lines, &rinex.record) {
+        (Some(lines), _) => {
+            let file_ts = file_timescale(rinex);
+            let ts = timescale.unwrap_or(file_ts);
+            for line in lines {
+                let epoch = line.date.and_then(|(y, m, d, hh, mm, ss)| {
+                    Epoch::maybe_from_gregorian(
+                        y,
+                        m,
+                        d,
+                        hh,
+                        mm,
+                        ss.trunc() as u8,
+                        (ss.fract() * 1e9).round() as u32,
+                        file_ts,
+                    )
+                    .ok()
+                });
+                if epoch.is_some_and(|epoch| !filter.keep_epoch(&epoch)) {
+                    continue;
+                }
+
+                epochs.push(epoch.map(|epoch| epoch_to_nanos(&epoch, ts)));
+                time_systems.push(ts.to_string());
+                epoch_flags.push(line.flag);
+                let count = line.count.map(|n| n as i64);
+                if line.is_event() {
+                    num_svs.push(None);
+                    event_lines.push(count);
+                } else {
+                    num_svs.push(count);
+                    event_lines.push(None);
+                }
+                clock_offsets.push(line.clock_offset);
+            }
+        },
+        (None, Record::ObsRecord(obs_data)) => {
+            for (obs_key, observations) in obs_data.iter() {
+                if !filter.keep_epoch(&obs_key.epoch) {
+                    continue;
+                }
+
+                let ts = timescale.unwrap_or(obs_key.epoch.time_scale);
+                let svs: BTreeSet<SV> = observations.signals.iter().map(|signal| signal.sv).collect();
+                epochs.push(Some(epoch_to_nanos(&obs_key.epoch, ts)));
+                time_systems.push(ts.to_string());
+                epoch_flags.push(epoch_flag_value(obs_key.flag));
+                num_svs.push(Some(svs.len() as i64));
+                event_lines.push(None);
+                clock_offsets.push(observations.clock.as_ref().map(|clock| clock.offset_s));
+            }
+        },
+        _ => {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "File does not contain observation data",
+            ));
+        }
+    }
+
+    let mut df = df![
+        "time_system" => &time_systems,
+        "epoch_flag" => &epoch_flags,
+        "num_sv" => &num_svs,
+        "event_lines" => &event_lines,
+        "clock_offset" => &clock_offsets,
+    ]
+    .map_err(polars_error)?;
+
+    df.insert_column(0, datetime_series_opt("epoch", epochs))
+        .map_err(polars_error)?;
+
+    Ok(df)
+}
+
+// Long observation frame: one row per (epoch, sv, observable)
+pub(crate) fn obs_long_frame(
+    rinex: &Rinex,
+    timescale: Option<TimeScale>,
+    filter: &ObsFilter,
+) -> PyResult<DataFrame> {
+    let mut epochs = Vec::new();
+    let mut time_systems = Vec::new();
+    let mut prns = Vec::new();
+    let mut codes = Vec::new();
+    let mut values = Vec::new();
+    let mut lli_flags = Vec::new();
+    let mut ssi_flags = Vec::new();
+    let mut loss_of_lock = Vec::new();
+    let mut half_cycle = Vec::new();
+    let mut anti_spoofing = Vec::new();
+    let mut epoch_flags = Vec::new();
+    let mut clock_offsets = Vec::new();
+
+    // Access the record containing observation data
+    match &rinex.record {
+        Record::ObsRecord(obs_data) => {
+            for (obs_key, observations) in obs_data.iter() {
+                if !filter.keep_epoch(&obs_key.epoch) {
+                    continue;
+                }
+
+                // Either the requested time scale, or the one the file is expressed in
+                let ts = timescale.unwrap_or(obs_key.epoch.time_scale);
+                let epoch_ns = epoch_to_nanos(&obs_key.epoch, ts);
+                let ts_name = ts.to_string();
+                let epoch_flag = epoch_flag_value(obs_key.flag);
+                // Receiver clock offset (s), when reported
+                let clock_offset = observations.clock.as_ref().map(|clock| clock.offset_s);
+
+                for signal in &observations.signals {
+                    if !filter.keep_sv(&signal.sv) {
+                        continue;
+                    }
+                    let observable = signal.observable.to_string();
+                    if !filter.keep_observable(&observable) {
+                        continue;
+                    }
+
+                    epochs.push(epoch_ns);
+                    time_systems.push(ts_name.clone());
+                    prns.push(signal.sv.to_string());
+                    codes.push(observable);
+                    values.push(signal.value);
+
+                    // Loss of Lock Indicator, decoded per bit
+                    let lli = signal.lli.map(|f| f.bits());
+                    lli_flags.push(lli.map(|b| b as i32));
+                    loss_of_lock.push(lli.map(|b| b & 0x01 != 0));
+                    half_cycle.push(lli.map(|b| b & 0x02 != 0));
+                    anti_spoofing.push(lli.map(|b| b & 0x04 != 0));
+
+                    // Signal Strength Indicator (1-9 scale, 0 if unknown)
+                    ssi_flags.push(signal.snr.map(ssi_value));
+
+                    epoch_flags.push(epoch_flag);
+                    clock_offsets.push(clock_offset);
+                }
+            }
+        },
+        _ => {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "File does not contain observation data",
+            ));
+        }
+    }
+
+    let mut df = df![
+        "time_system" => &time_systems,
+        "sv" => &prns,
+        "observable" => &codes,
+        "value" => &values,
+        "lli" => &lli_flags,
+        "ssi" => &ssi_flags,
+        "loss_of_lock" => &loss_of_lock,
+        "half_cycle" => &half_cycle,
+        "anti_spoofing" => &anti_spoofing,
+        "epoch_flag" => &epoch_flags,
+        "clock_offset" => &clock_offsets,
+    ]
+    .map_err(polars_error)?;
+
+    // Epochs are stored as a proper Datetime column, labelled UTC by polars
+    // but expressed in the scale reported by `time_system`
+    df.insert_column(0, datetime_series("epoch", epochs))
+        .map_err(polars_error)?;
+
+    Ok(df)
+}
+
+// Wide observation frame: one row per (epoch, sv), one column per observable
+pub(crate) fn obs_wide_frame(
+    rinex: &Rinex,
+    timescale: Option<TimeScale>,
+    filter: &ObsFilter,
+) -> PyResult<DataFrame> {
+    let obs_data = match &rinex.record {
+        Record::ObsRecord(obs_data) => obs_data,
+        _ => {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "File does not contain observation data",
+            ));
+        }
+    };
+
+    // One output column per (selected) observable, sorted by name
+    let observables: BTreeSet<String> = obs_data
+        .iter()
+        .filter(|(obs_key, _)| filter.keep_epoch(&obs_key.epoch))
+        .flat_map(|(_, observations)| observations.signals.iter())
+        .filter(|signal| filter.keep_sv(&signal.sv))
+        .map(|signal| signal.observable.to_string())
+        .filter(|observable| filter.keep_observable(observable))
+        .collect();
+    let column_index: HashMap<&str, usize> = observables
+        .iter()
+        .enumerate()
+        .map(|(i, name)| (name.as_str(), i))
+        .collect();
+
+    let mut epochs = Vec::new();
+    let mut time_systems = Vec::new();
+    let mut prns = Vec::new();
+    let mut epoch_flags = Vec::new();
+    let mut clock_offsets = Vec::new();
+    let mut columns: Vec<Vec<Option<f64>>> = vec![Vec::new(); observables.len()];
+
+    for (obs_key, observations) in obs_data.iter() {
+        if !filter.keep_epoch(&obs_key.epoch) {
+            continue;
+        }
+
+        let ts = timescale.unwrap_or(obs_key.epoch.time_scale);
+        let epoch_ns = epoch_to_nanos(&obs_key.epoch, ts);
+        let ts_name = ts.to_string();
+        let epoch_flag = epoch_flag_value(obs_key.flag);
+        let clock_offset = observations.clock.as_ref().map(|clock| clock.offset_s);
+
+        // Gather the signals of this epoch into one row per SV
+        let mut rows: BTreeMap<SV, Vec<Option<f64>>> = BTreeMap::new();
+        for signal in &observations.signals {
+            if !filter.keep_sv(&signal.sv) {
+                continue;
+            }
+            let observable = signal.observable.to_string();
+            let Some(&index) = column_index.get(observable.as_str()) else {
+                continue;
+            };
+
+            let row = rows
+                .entry(signal.sv)
+                .or_insert_with(|| vec![None; observables.len()]);
+            let cell = &mut row[index];
+            if cell.is_none() {
+                *cell = Some(signal.value);
+            }
+        }
+
+        for (sv, row) in rows {
+            epochs.push(epoch_ns);
+            time_systems.push(ts_name.clone());
+            prns.push(sv.to_string());
+            epoch_flags.push(epoch_flag);
+            clock_offsets.push(clock_offset);
+            for (column, value) in columns.iter_mut().zip(row) {
+                column.push(value);
+            }
+        }
+    }
+
+    let mut df = df![
+        "time_system" => &time_systems,
+        "sv" => &prns,
+        "epoch_flag" => &epoch_flags,
+        "clock_offset" => &clock_offsets,
+    ]
+    .map_err(polars_error)?;
+
+    df.insert_column(0, datetime_series("epoch", epochs))
+        .map_err(polars_error)?;
+
+    for (name, values) in observables.iter().zip(columns) {
+        let series = Float64Chunked::from_iter_options(name.as_str().into(), values.into_iter())
+            .into_series();
+        df.with_column(series)
+            .map_err(polars_error)?;
+    }
+
+    Ok(df)
+}
+
+// Where the observation readers take their content from
+#[derive(Clone, Copy)]
+enum ObsSource<'a> {
+    Path(&'a Path),
+    // `bytes`, any buffer-protocol object, or a binary file-like object
+    // (gzip is detected), already read
+    Bytes(&'a [u8]),
+}
+
+impl ObsSource<'_> {
+    fn parse(self, lenient: bool) -> PyResult<(Rinex, Vec<SkippedEpoch>)> {
+        match self {
+            ObsSource::Path(path) => {
+                check_exists(path)?;
+                Ok(parse_obs_file(path, lenient)?)
+            },
+            ObsSource::Bytes(content) => {
+                let (rinex, skipped) = match rinex_from_bytes(content) {
+                    Ok(rinex) => (rinex, Vec::new()),
+                    Err(e) => {
+                        let text = String::from_utf8_lossy(content);
+                        match lenient.then(|| parse_lenient(&text)).flatten() {
+                            Some(parsed) => parsed,
+                            None => return Err(locate_corrupted(&text).unwrap_or(e).into()),
+                        }
+                    },
+                };
+                Ok((ensure_observation(rinex)?, skipped))
+            },
+        }
+    }
+
+    // Header dict of the parsed content: `rinex.header`, completed by the
+    // records only found in the header lines (read again up to END OF HEADER)
+    fn header<'py>(self, py: Python<'py>, rinex: &Rinex) -> PyResult<Bound<'py, PyDict>> {
+        let extras = match self {
+            ObsSource::Path(path) => HeaderExtras::from_path(path)
+                .map_err(|e| RinexError::parse(format!("RINEX header error: {}", e)).with_path(path))?,
+            ObsSource::Bytes(content) => HeaderExtras::from_reader(Cursor::new(content))
+                .map_err(|e| RinexError::parse(format!("RINEX header error: {}", e)))?,
+        };
+        obs_header_dict(py, &rinex.header, &extras)
+    }
+
+    // Per-epoch table of the parsed content, from its epoch lines (read again)
+    fn epochs(self, rinex: &Rinex, timescale: Option<TimeScale>, filter: &ObsFilter) -> PyResult<DataFrame> {
+        let lines = match self {
+            ObsSource::Path(path) => {
+                let reader = open_text_reader(path)
+                    .map_err(|e| RinexError::parse(format!("RINEX read error: {}", e)).with_path(path))?;
+                scan_epoch_lines(reader).map_err(|e| e.with_path(path))?
+            },
+            ObsSource::Bytes(content) => scan_epoch_lines(Cursor::new(content))?,
+        };
+        obs_epochs_frame(rinex, lines.as_deref(), timescale, filter)
+    }
+}
+
+// What the observation readers return on top of the data
+#[derive(Clone, Copy)]
+struct ObsFlags {
+    lenient: bool,
+    with_header: bool,
+    with_epochs: bool,
+}
+
+// Shared body of `read_rinex_obs`, `read_rinex_obs_wide` and `read_rinex_obs_bytes`
+fn read_obs_impl<'py>(
+    py: Python<'py>,
+    source: ObsSource<'_>,
+    timescale: Option<TimeScale>,
+    filter: &ObsFilter,
+    wide: bool,
+    flags: ObsFlags,
+) -> PyResult<Bound<'py, PyAny>> {
+    let (rinex, skipped) = source.parse(flags.lenient)?;
+    let header = flags.with_header.then(|| source.header(py, &rinex)).transpose()?;
+    let epochs = flags
+        .with_epochs
+        .then(|| source.epochs(&rinex, timescale, filter))
+        .transpose()?;
+
+    let df = if wide {
+        obs_wide_frame(&rinex, timescale, filter)?
+    } else {
+        obs_long_frame(&rinex, timescale, filter)?
+    };
+
+    obs_output(py, df, &rinex, header, epochs, flags.lenient.then_some(skipped.as_slice()))
+}
+
+#[pyfunction]
+#[pyo3(signature = (
+    path,
+    timescale=None,
+    constellations=None,
+    svs=None,
+    observables=None,
+    observable_regex=None,
+    start=None,
+    end=None,
+    interval=None,
+    lenient=false,
+    with_header=false,
+    with_epochs=false,
+))]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn read_rinex_obs<'py>(
+    py: Python<'py>,
+    path: &str,
+    timescale: Option<&str>,
+    constellations: Option<Vec<String>>,
+    svs: Option<Vec<String>>,
+    observables: Option<Vec<String>>,
+    observable_regex: Option<&str>,
+    start: Option<&str>,
+    end: Option<&str>,
+    interval: Option<f64>,
+    lenient: bool,
+    with_header: bool,
+    with_epochs: bool,
+) -> PyResult<Bound<'py, PyAny>> {
+    let timescale = timescale.map(parse_timescale).transpose()?;
+    let filter = ObsFilter::new(constellations, svs, observables, observable_regex, start, end, interval)?;
+    let flags = ObsFlags { lenient, with_header, with_epochs };
+    read_obs_impl(py, ObsSource::Path(Path::new(path)), timescale, &filter, false, flags)
+}
+
+#[pyfunction]
+#[pyo3(signature = (
+    path,
+    timescale=None,
+    constellations=None,
+    svs=None,
+    observables=None,
+    observable_regex=None,
+    start=None,
+    end=None,
+    interval=None,
+    lenient=false,
+    with_header=false,
+    with_epochs=false,
+))]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn read_rinex_obs_wide<'py>(
+    py: Python<'py>,
+    path: &str,
+    timescale: Option<&str>,
+    constellations: Option<Vec<String>>,
+    svs: Option<Vec<String>>,
+    observables: Option<Vec<String>>,
+    observable_regex: Option<&str>,
+    start: Option<&str>,
+    end: Option<&str>,
+    interval: Option<f64>,
+    lenient: bool,
+    with_header: bool,
+    with_epochs: bool,
+) -> PyResult<Bound<'py, PyAny>> {
+    let timescale = timescale.map(parse_timescale).transpose()?;
+    let filter = ObsFilter::new(constellations, svs, observables, observable_regex, start, end, interval)?;
+    let flags = ObsFlags { lenient, with_header, with_epochs };
+    read_obs_impl(py, ObsSource::Path(Path::new(path)), timescale, &filter, true, flags)
+}
+
+// Same as `read_rinex_obs`, for content already in memory: `bytes`, any
+// buffer-protocol object, or a binary file-like object (gzip is detected)
+#[pyfunction]
+#[pyo3(signature = (
+    data,
+    timescale=None,
+    constellations=None,
+    svs=None,
+    observables=None,
+    observable_regex=None,
+    start=None,
+    end=None,
+    interval=None,
+    lenient=false,
+    with_header=false,
+    with_epochs=false,
+))]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn read_rinex_obs_bytes<'py>(
+    py: Python<'py>,
+    data: &Bound<'py, PyAny>,
+    timescale: Option<&str>,
+    constellations: Option<Vec<String>>,
+    svs: Option<Vec<String>>,
+    observables: Option<Vec<String>>,
+    observable_regex: Option<&str>,
+    start: Option<&str>,
+    end: Option<&str>,
+    interval: Option<f64>,
+    lenient: bool,
+    with_header: bool,
+    with_epochs: bool,
+) -> PyResult<Bound<'py, PyAny>> {
+    let timescale = timescale.map(parse_timescale).transpose()?;
+    let filter = ObsFilter::new(constellations, svs, observables, observable_regex, start, end, interval)?;
+    let flags = ObsFlags { lenient, with_header, with_epochs };
+    let content = read_buffer(data)?;
+    read_obs_impl(py, ObsSource::Bytes(&content), timescale, &filter, false, flags)
+}
@@ -0,0 +1,416 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use regex::Regex;
+use rinex::prelude::*;
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Cursor};
+use std::path::Path;
+use std::sync::OnceLock;
+
+use crate::error::RinexError;
+use crate::header::{open_text_reader, parse_f64, parse_i32};
+use crate::obs::check_exists;
+use crate::time::calendar_to_nanos;
+
+// Epoch line of a RINEX 2 observation file (" yy mm dd hh mm ss.sssssss  f")
+fn v2_epoch_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"^ [ \d]\d [ \d]\d [ \d]\d [ \d]\d [ \d]\d [ \d]\d\.\d{7}  [0-6]").unwrap()
+    })
+}
+
+pub(crate) fn is_epoch_start(line: &str, is_v2: bool) -> bool {
+    if is_v2 {
+        v2_epoch_regex().is_match(line)
+    } else {
+        line.starts_with('>')
+    }
+}
+
+pub(crate) fn is_end_of_header(line: &str) -> bool {
+    line.get(60..).is_some_and(|label| label.trim() == "END OF HEADER")
+}
+
+// Raw text of one epoch record, and the (1-based) line number it starts at
+pub(crate) struct EpochBlock {
+    pub line: usize,
+    pub text: String,
+}
+
+impl EpochBlock {
+    pub(crate) fn end_line(&self) -> usize {
+        self.line + self.text.lines().count().max(1) - 1
+    }
+
+    // Date and time fields of the epoch line, as written in the file
+    pub(crate) fn epoch(&self, is_v2: bool) -> Option<String> {
+        let first_line = self.text.lines().next()?;
+        let fields = if is_v2 {
+            first_line.get(1..26)
+        } else {
+            first_line.get(2..29)
+        };
+        fields.map(|f| f.trim().to_string()).filter(|f| !f.is_empty())
+    }
+}
+
+// Epoch record dropped by the lenient parser
+#[derive(Debug, Clone)]
+pub(crate) struct SkippedEpoch {
+    pub start_line: usize,
+    pub end_line: usize,
+    pub epoch: Option<String>,
+    pub reason: String,
+}
+
+impl SkippedEpoch {
+    pub(crate) fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("start_line", self.start_line)?;
+        dict.set_item("end_line", self.end_line)?;
+        dict.set_item("epoch", &self.epoch)?;
+        dict.set_item("reason", &self.reason)?;
+        Ok(dict)
+    }
+}
+
+// Plain (not Hatanaka-compressed) observation file, split into its header
+// and its epoch records
+pub(crate) struct ObsText {
+    pub header: String,
+    pub is_v2: bool,
+    pub blocks: Vec<EpochBlock>,
+}
+
+impl ObsText {
+    pub(crate) fn split(content: &str) -> Option<Self> {
+        let mut lines = content.split_inclusive('\n').enumerate();
+
+        let mut header = String::new();
+        for (_, line) in lines.by_ref() {
+            header.push_str(line);
+            if is_end_of_header(line) {
+                break;
+            }
+        }
+
+        let first_line = header.lines().next()?;
+        if first_line.get(20..21) != Some("O") || !header.lines().any(is_end_of_header) {
+            return None;
+        }
+        let is_v2 = first_line.get(0..9).is_some_and(|v| v.trim().starts_with('2'));
+
+        // Lines before the first epoch (if any) are attached to it
+        let mut blocks: Vec<EpochBlock> = Vec::new();
+        for (index, line) in lines {
+            match blocks.last_mut() {
+                Some(block) if !is_epoch_start(line, is_v2) => block.text.push_str(line),
+                _ => blocks.push(EpochBlock {
+                    line: index + 1,
+                    text: line.to_string(),
+                }),
+            }
+        }
+
+        Some(Self { header, is_v2, blocks })
+    }
+
+    // Parses a selection of epoch records, behind a copy of the header
+    fn parse_blocks<'a>(&self, blocks: impl IntoIterator<Item = &'a EpochBlock>) -> Result<Rinex, String> {
+        let mut text = self.header.clone();
+        for block in blocks {
+            text.push_str(&block.text);
+        }
+        Rinex::from_reader(&mut BufReader::new(Cursor::new(text.into_bytes())))
+            .map_err(|e| e.to_string())
+    }
+
+    // Epoch records (index, reason) that cannot be parsed, found by bisection
+    // so that only a few parses are needed when most of the file is valid
+    fn find_corrupted(&self, offset: usize, blocks: &[EpochBlock], corrupted: &mut Vec<(usize, String)>) {
+        if blocks.is_empty() {
+            return;
+        }
+        if let Err(reason) = self.parse_blocks(blocks) {
+            if blocks.len() == 1 {
+                corrupted.push((offset, reason));
+            } else {
+                let mid = blocks.len() / 2;
+                self.find_corrupted(offset, &blocks[..mid], corrupted);
+                self.find_corrupted(offset + mid, &blocks[mid..], corrupted);
+            }
+        }
+    }
+
+    fn corrupted(&self) -> Vec<(usize, String)> {
+        let mut corrupted = Vec::new();
+        self.find_corrupted(0, &self.blocks, &mut corrupted);
+        corrupted
+    }
+}
+
+// Epoch line of an observation file, event epochs included
+pub(crate) struct EpochLine {
+    // Date and time as written in the file (blank for some events)
+    pub date: Option<(i32, u8, u8, u8, u8, f64)>,
+    pub flag: i32,
+    // Number of satellites, or of special records following an event (flags 2-5)
+    pub count: Option<usize>,
+    pub clock_offset: Option<f64>,
+}
+
+impl EpochLine {
+    fn parse(line: &str, is_v2: bool) -> Option<Self> {
+        let (date, flag, count, clock_offset) = if is_v2 {
+            let date = parse_i32(line, 1, 3).and_then(|yy| {
+                Some((
+                    if yy < 80 { 2000 + yy } else { 1900 + yy },
+                    parse_i32(line, 4, 6)? as u8,
+                    parse_i32(line, 7, 9)? as u8,
+                    parse_i32(line, 10, 12)? as u8,
+                    parse_i32(line, 13, 15)? as u8,
+                    parse_f64(line, 15, 26)?,
+                ))
+            });
+            (date, parse_i32(line, 28, 29)?, parse_i32(line, 29, 32), parse_f64(line, 68, 80))
+        } else {
+            let date = parse_i32(line, 2, 6).and_then(|year| {
+                Some((
+                    year,
+                    parse_i32(line, 7, 9)? as u8,
+                    parse_i32(line, 10, 12)? as u8,
+                    parse_i32(line, 13, 15)? as u8,
+                    parse_i32(line, 16, 18)? as u8,
+                    parse_f64(line, 18, 29)?,
+                ))
+            });
+            (date, parse_i32(line, 31, 32)?, parse_i32(line, 32, 35), parse_f64(line, 41, 56))
+        };
+
+        // Only events may leave their date blank
+        if date.is_none() && !(2..=5).contains(&flag) {
+            return None;
+        }
+
+        Some(Self {
+            date,
+            flag,
+            count: count.map(|n| n.max(0) as usize),
+            clock_offset,
+        })
+    }
+
+    pub(crate) fn is_event(&self) -> bool {
+        (2..=5).contains(&self.flag)
+    }
+}
+
+// RINEX 2 event epoch line whose date is left blank
+fn is_v2_blank_event(line: &str) -> bool {
+    line.get(..28).is_some_and(|date| date.trim().is_empty())
+        && matches!(line.get(28..29), Some("2" | "3" | "4" | "5"))
+}
+
+// Epoch lines of a plain (not Hatanaka-compressed) observation file. The
+// special records following an event are skipped, so that header records
+// found there are never mistaken for epochs; malformed epoch lines are left
+// to the parser. None for other files.
+pub(crate) fn scan_epoch_lines<R: BufRead>(reader: R) -> Result<Option<Vec<EpochLine>>, RinexError> {
+    let mut is_v2 = false;
+    let mut in_header = true;
+    let mut epochs: Vec<EpochLine> = Vec::new();
+    // Special records of the last event, still to be skipped
+    let mut to_skip = 0;
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.map_err(|e| RinexError::parse(format!("RINEX read error: {}", e)))?;
+        if in_header {
+            if index == 0 {
+                let label = line.get(60..).unwrap_or("").trim();
+                if label.starts_with("CRINEX") || line.get(20..21) != Some("O") {
+                    return Ok(None);
+                }
+                is_v2 = line.get(0..9).is_some_and(|v| v.trim().starts_with('2'));
+            }
+            in_header = !is_end_of_header(&line);
+            continue;
+        }
+        if to_skip > 0 {
+            to_skip -= 1;
+            continue;
+        }
+
+        if !is_epoch_start(&line, is_v2) && !(is_v2 && is_v2_blank_event(&line)) {
+            continue;
+        }
+        if let Some(epoch) = EpochLine::parse(&line, is_v2) {
+            if epoch.is_event() {
+                to_skip = epoch.count.unwrap_or(0);
+            }
+            epochs.push(epoch);
+        }
+    }
+
+    Ok(Some(epochs))
+}
+
+// Parses what can be parsed, dropping corrupted epoch records: parsing
+// resumes at the next epoch line. None when the content cannot be split
+// into epochs (e.g. Hatanaka-compressed files) or is still not parsable.
+pub(crate) fn parse_lenient(content: &str) -> Option<(Rinex, Vec<SkippedEpoch>)> {
+    let text = ObsText::split(content)?;
+    let corrupted: BTreeMap<usize, String> = text.corrupted().into_iter().collect();
+
+    let rinex = text
+        .parse_blocks(
+            text.blocks
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| !corrupted.contains_key(index))
+                .map(|(_, block)| block),
+        )
+        .ok()?;
+
+    let skipped = corrupted
+        .into_iter()
+        .map(|(index, reason)| {
+            let block = &text.blocks[index];
+            SkippedEpoch {
+                start_line: block.line,
+                end_line: block.end_line(),
+                epoch: block.epoch(text.is_v2),
+                reason,
+            }
+        })
+        .collect();
+
+    Some((rinex, skipped))
+}
+
+// First corrupted epoch record of content the parser rejected, with its
+// line and epoch. None when the failure cannot be traced to one epoch
+// record (header at fault, Hatanaka-compressed content...).
+pub(crate) fn locate_corrupted(content: &str) -> Option<RinexError> {
+    let text = ObsText::split(content)?;
+    text.parse_blocks([]).ok()?;
+
+    let (index, reason) = text.corrupted().into_iter().next()?;
+    let block = &text.blocks[index];
+    Some(RinexError::corrupted_epoch(
+        format!("RINEX parsing error: {}", reason),
+        block.line,
+        block.epoch(text.is_v2),
+    ))
+}
+
+// RINEX 4 navigation message other than an ephemeris ("> ION G01 LNAV",
+// "> STO E05 IFNV" ...), with its data lines
+pub(crate) struct NavMessage {
+    pub kind: String,
+    pub sv: String,
+    pub msg_type: String,
+    pub lines: Vec<String>,
+}
+
+// Navigation file scanned line by line: header records, and RINEX 4
+// ION/STO/EOP messages (ephemerides are left to the `rinex` parser)
+pub(crate) struct NavText {
+    pub header: Vec<String>,
+    pub messages: Vec<NavMessage>,
+}
+
+impl NavText {
+    pub(crate) fn from_reader<R: BufRead>(reader: R) -> Result<Self, RinexError> {
+        let mut header = Vec::new();
+        let mut messages: Vec<NavMessage> = Vec::new();
+        let mut in_header = true;
+        // Whether the lines being read belong to the last message
+        let mut in_message = false;
+
+        for line in reader.lines() {
+            let line = line.map_err(|e| RinexError::parse(format!("RINEX read error: {}", e)))?;
+            if in_header {
+                if is_end_of_header(&line) {
+                    in_header = false;
+                }
+                header.push(line);
+                continue;
+            }
+
+            if line.starts_with('>') {
+                let tokens: Vec<&str> = line[1..].split_whitespace().collect();
+                in_message = matches!(tokens.first(), Some(&"ION") | Some(&"STO") | Some(&"EOP"));
+                if in_message {
+                    messages.push(NavMessage {
+                        kind: tokens[0].to_string(),
+                        sv: tokens.get(1).unwrap_or(&"").to_string(),
+                        msg_type: tokens.get(2).unwrap_or(&"").to_string(),
+                        lines: Vec::new(),
+                    });
+                }
+            } else if in_message {
+                if let Some(message) = messages.last_mut() {
+                    message.lines.push(line);
+                }
+            }
+        }
+
+        if in_header {
+            return Err(RinexError::parse("RINEX header is missing END OF HEADER"));
+        }
+        let file_type = header.first().and_then(|line| line.get(20..21));
+        if !matches!(file_type, Some("N") | Some("G") | Some("H")) {
+            return Err(RinexError::wrong_type("This is not a RINEX Navigation file"));
+        }
+
+        Ok(Self { header, messages })
+    }
+
+    pub(crate) fn from_path(path: &Path) -> PyResult<Self> {
+        check_exists(path)?;
+        let reader = open_text_reader(path)
+            .map_err(|e| RinexError::parse(format!("RINEX read error: {}", e)).with_path(path))?;
+        Self::from_reader(reader).map_err(|e| e.with_path(path).into())
+    }
+
+    // Header records with the given label
+    pub(crate) fn records<'a>(&'a self, label: &'a str) -> impl Iterator<Item = &'a String> + 'a {
+        self.header
+            .iter()
+            .filter(move |line| line.get(60..).is_some_and(|l| l.trim() == label))
+    }
+
+    pub(crate) fn messages<'a>(&'a self, kind: &'a str) -> impl Iterator<Item = &'a NavMessage> + 'a {
+        self.messages.iter().filter(move |m| m.kind == kind)
+    }
+}
+
+impl NavMessage {
+    // Transmission time of the message (first data line, "    yyyy mm dd hh mm ss"),
+    // as written in the file
+    pub(crate) fn epoch_nanos(&self) -> Option<i64> {
+        let line = self.lines.first()?;
+        Some(calendar_to_nanos(
+            parse_i32(line, 4, 8)?,
+            parse_i32(line, 9, 11)? as u8,
+            parse_i32(line, 12, 14)? as u8,
+            parse_i32(line, 15, 17)? as u8,
+            parse_i32(line, 18, 20)? as u8,
+            parse_i32(line, 21, 23)? as u8,
+            0,
+        ))
+    }
+
+    // Numeric fields (D19.12) of the message: after the epoch on the first
+    // line, from column 4 on the following ones
+    pub(crate) fn values(&self) -> Vec<Option<f64>> {
+        let mut values = Vec::new();
+        for (i, line) in self.lines.iter().enumerate() {
+            let (start, count) = if i == 0 { (23, 3) } else { (4, 4) };
+            for k in 0..count {
+                values.push(parse_f64(line, start + 19 * k, start + 19 * (k + 1)));
+            }
+        }
+        values
+    }
+}
@@ -0,0 +1,234 @@
+use polars::prelude::*;
+use pyo3::prelude::*;
+use pyo3_polars::PyDataFrame;
+use std::collections::{HashMap, HashSet};
+
+use crate::error::polars_error;
+use crate::health::screen_records;
+use crate::time::{datetime_series, gpst_offset_ns, week_origin_s};
+
+const NS: i64 = 1_000_000_000;
+const WEEK_S: i64 = 604_800;
+
+fn missing_column(name: &str) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Missing column '{}'", name))
+}
+
+// Datetime column as nanoseconds, whatever its time unit
+pub(crate) fn datetime_column(df: &DataFrame, name: &str) -> PyResult<Vec<Option<i64>>> {
+    let series = df.column(name).map_err(|_| missing_column(name))?.as_materialized_series();
+    let factor = match series.dtype() {
+        DataType::Datetime(TimeUnit::Nanoseconds, _) => 1,
+        DataType::Datetime(TimeUnit::Microseconds, _) => 1_000,
+        DataType::Datetime(TimeUnit::Milliseconds, _) => 1_000_000,
+        other => {
+            return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+                format!("Column '{}' must be a Datetime, not {}", name, other)
+            ));
+        },
+    };
+    let values = series.cast(&DataType::Int64).map_err(polars_error)?;
+    Ok(values
+        .i64()
+        .map_err(polars_error)?
+        .into_iter()
+        .map(|v| v.map(|v| v * factor))
+        .collect())
+}
+
+pub(crate) fn str_column(df: &DataFrame, name: &str) -> PyResult<Vec<Option<String>>> {
+    let series = df.column(name).map_err(|_| missing_column(name))?.as_materialized_series();
+    Ok(series
+        .str()
+        .map_err(polars_error)?
+        .into_iter()
+        .map(|v| v.map(str::to_string))
+        .collect())
+}
+
+// Numeric column as f64, all missing when the column does not exist
+pub(crate) fn opt_f64_column(df: &DataFrame, name: &str) -> PyResult<Vec<Option<f64>>> {
+    match df.column(name) {
+        Ok(column) => {
+            let values = column
+                .as_materialized_series()
+                .cast(&DataType::Float64)
+                .map_err(polars_error)?;
+            Ok(values.f64().map_err(polars_error)?.into_iter().collect())
+        },
+        Err(_) => Ok(vec![None; df.height()]),
+    }
+}
+
+// Half width of the interval an ephemeris can be used in, around its toe.
+// For GPS this is the broadcast fit interval (hours, or flag), for QZSS the
+// fit interval flag (0: 2 h, 1: longer than 2 h).
+fn half_fit_ns(letter: char, fit_interval: Option<f64>) -> i64 {
+    let hours = match letter {
+        'G' => match fit_interval {
+            Some(fit) if fit > 1.0 => fit,
+            Some(fit) if fit == 1.0 => 6.0,
+            _ => 4.0,
+        },
+        'J' => match fit_interval {
+            Some(fit) if fit > 1.0 => fit,
+            Some(fit) if fit == 1.0 => 4.0,
+            _ => 2.0,
+        },
+        'R' => 0.5,
+        'S' => 10.0 / 60.0,
+        _ => 4.0,
+    };
+    (hours * 1800.0 * 1e9) as i64
+}
+
+// Ephemeris record usable for selection
+struct Candidate {
+    row: usize,
+    // Reference time (toe, or toc for GLONASS/SBAS) in GPST nanoseconds
+    reference_ns: i64,
+    half_fit_ns: i64,
+    t_tm: f64,
+    iode: f64,
+}
+
+// Reference time of each nav record, in GPST: toe is given as seconds of
+// week, the week being the one closest to toc
+fn reference_times(
+    toc: &[Option<i64>],
+    time_systems: &[Option<String>],
+    toe: &[Option<f64>],
+) -> PyResult<Vec<Option<i64>>> {
+    toc.iter()
+        .zip(time_systems)
+        .zip(toe)
+        .map(|((toc, ts), toe)| {
+            let Some(toc) = *toc else {
+                return Ok(None);
+            };
+            let ts = ts.as_deref().unwrap_or("GPST");
+            let reference = match toe {
+                Some(toe) => {
+                    let toc_sow = (toc.div_euclid(NS) - week_origin_s(ts)).rem_euclid(WEEK_S);
+                    let mut dt = *toe - toc_sow as f64;
+                    if dt > (WEEK_S / 2) as f64 {
+                        dt -= WEEK_S as f64;
+                    } else if dt < -(WEEK_S / 2) as f64 {
+                        dt += WEEK_S as f64;
+                    }
+                    toc + (dt * 1e9).round() as i64
+                },
+                None => toc,
+            };
+            Ok(Some(reference + gpst_offset_ns(ts, reference)?))
+        })
+        .collect()
+}
+
+// For each distinct (sv, epoch) of `epochs`, the ephemeris of `nav` whose
+// reference time is the closest, within its fit interval. Ties (e.g. a new
+// upload with a different IODE and the same toe) go to the latest transmitted
+// record, then to the highest IODE.
+#[pyfunction]
+#[pyo3(signature = (nav, epochs, healthy_only=true))]
+pub(crate) fn select_ephemeris(
+    nav: PyDataFrame,
+    epochs: PyDataFrame,
+    healthy_only: bool,
+) -> PyResult<PyDataFrame> {
+    let nav: DataFrame = nav.into();
+    let epochs: DataFrame = epochs.into();
+
+    // Navigation records, grouped by SV
+    let nav_svs = str_column(&nav, "sv")?;
+    let toc = datetime_column(&nav, "toc")?;
+    let nav_time_systems = match nav.column("time_system") {
+        Ok(_) => str_column(&nav, "time_system")?,
+        Err(_) => vec![None; nav.height()],
+    };
+    let references = reference_times(&toc, &nav_time_systems, &opt_f64_column(&nav, "toe")?)?;
+    let fit_intervals = opt_f64_column(&nav, "fitInt")?;
+    let t_tm = opt_f64_column(&nav, "t_tm")?;
+    let iode = opt_f64_column(&nav, "iode")?;
+    let healthy: Vec<bool> = screen_records(&nav, None)?.iter().map(Option::is_none).collect();
+
+    let mut candidates: HashMap<&str, Vec<Candidate>> = HashMap::new();
+    for (row, sv) in nav_svs.iter().enumerate() {
+        let (Some(sv), Some(reference_ns)) = (sv.as_deref(), references[row]) else {
+            continue;
+        };
+        if healthy_only && !healthy[row] {
+            continue;
+        }
+        let letter = sv.chars().next().unwrap_or('G');
+        candidates.entry(sv).or_default().push(Candidate {
+            row,
+            reference_ns,
+            half_fit_ns: half_fit_ns(letter, fit_intervals[row]),
+            t_tm: t_tm[row].unwrap_or(f64::NEG_INFINITY),
+            iode: iode[row].unwrap_or(f64::NEG_INFINITY),
+        });
+    }
+
+    // Distinct (epoch, sv) pairs, in order of appearance
+    let epoch_values = datetime_column(&epochs, "epoch")?;
+    let epoch_svs = str_column(&epochs, "sv")?;
+    let epoch_time_systems = match epochs.column("time_system") {
+        Ok(_) => str_column(&epochs, "time_system")?,
+        Err(_) => vec![None; epochs.height()],
+    };
+
+    let mut seen = HashSet::new();
+    let mut out_epochs = Vec::new();
+    let mut out_svs = Vec::new();
+    let mut out_dt = Vec::new();
+    let mut out_rows = Vec::new();
+    for ((epoch, sv), ts) in epoch_values.into_iter().zip(epoch_svs).zip(epoch_time_systems) {
+        let (Some(epoch), Some(sv)) = (epoch, sv) else {
+            continue;
+        };
+        if !seen.insert((epoch, sv.clone())) {
+            continue;
+        }
+        let epoch_gpst = epoch + gpst_offset_ns(ts.as_deref().unwrap_or("GPST"), epoch)?;
+
+        let best = candidates
+            .get(sv.as_str())
+            .into_iter()
+            .flatten()
+            .filter(|c| (epoch_gpst - c.reference_ns).abs() <= c.half_fit_ns)
+            .min_by(|a, b| {
+                (epoch_gpst - a.reference_ns)
+                    .abs()
+                    .cmp(&(epoch_gpst - b.reference_ns).abs())
+                    .then(b.t_tm.total_cmp(&a.t_tm))
+                    .then(b.iode.total_cmp(&a.iode))
+                    .then(b.row.cmp(&a.row))
+            });
+
+        out_epochs.push(epoch);
+        out_svs.push(sv);
+        out_dt.push(best.map(|c| (epoch_gpst - c.reference_ns) as f64 * 1e-9));
+        out_rows.push(best.map(|c| c.row as IdxSize));
+    }
+
+    // Parameters of the selected records (all missing when none is valid)
+    let keep: Vec<PlSmallStr> = nav
+        .get_column_names()
+        .into_iter()
+        .filter(|name| name.as_str() != "sv" && name.as_str() != "row_id")
+        .cloned()
+        .collect();
+    let rows = IdxCa::from_iter_options("row".into(), out_rows.into_iter());
+    let selected = nav.select(keep).map_err(polars_error)?.take(&rows).map_err(polars_error)?;
+
+    let mut df = df![
+        "sv" => &out_svs,
+        "dt" => &out_dt,
+    ]
+    .map_err(polars_error)?;
+    df.insert_column(0, datetime_series("epoch", out_epochs)).map_err(polars_error)?;
+    df.hstack_mut(selected.get_columns()).map_err(polars_error)?;
+
+    Ok(PyDataFrame(df))
+}
@@ -0,0 +1,389 @@
+use polars::prelude::*;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use pyo3_polars::PyDataFrame;
+use std::collections::BTreeMap;
+use std::io::BufRead;
+use std::path::Path;
+
+use crate::error::{polars_error, RinexError};
+use crate::header::{field, open_text_reader, parse_f64, parse_i32};
+use crate::obs::check_exists;
+use crate::time::{calendar_to_nanos, datetime_series};
+
+// Missing clock (and clock rate) value
+const BAD_CLOCK: f64 = 999_999.0;
+
+// SP3 header: first two lines, "+ " satellite and "++" accuracy lines,
+// "%c" / "%f" descriptors and "/*" comments
+#[derive(Debug, Default)]
+struct Sp3Header {
+    version: String,
+    data_type: String,
+    start: Option<String>,
+    num_epochs: Option<i32>,
+    data_used: Option<String>,
+    coordinate_system: Option<String>,
+    orbit_type: Option<String>,
+    agency: Option<String>,
+    gps_week: Option<i32>,
+    seconds_of_week: Option<f64>,
+    epoch_interval: Option<f64>,
+    mjd: Option<i32>,
+    fractional_day: Option<f64>,
+    file_type: Option<String>,
+    time_system: String,
+    base_pos: Option<f64>,
+    base_clk: Option<f64>,
+    satellites: Vec<String>,
+    accuracy_exponents: Vec<Option<i32>>,
+    comments: Vec<String>,
+}
+
+// State vector of one satellite at one epoch, in SI units
+#[derive(Debug, Default)]
+struct Sp3Record {
+    epoch: i64,
+    sv: String,
+    x: Option<f64>,
+    y: Option<f64>,
+    z: Option<f64>,
+    clock: Option<f64>,
+    x_std: Option<f64>,
+    y_std: Option<f64>,
+    z_std: Option<f64>,
+    clock_std: Option<f64>,
+    clock_event: bool,
+    clock_pred: bool,
+    maneuver: bool,
+    orbit_pred: bool,
+    vx: Option<f64>,
+    vy: Option<f64>,
+    vz: Option<f64>,
+    clock_rate: Option<f64>,
+    vx_std: Option<f64>,
+    vy_std: Option<f64>,
+    vz_std: Option<f64>,
+    clock_rate_std: Option<f64>,
+}
+
+// Time system names used by the other readers (GPST, GST, BDT...)
+pub(crate) fn time_system_name(sp3_name: Option<&str>) -> String {
+    match sp3_name {
+        Some("GAL") => "GST",
+        Some("BDT") | Some("BDS") => "BDT",
+        Some("QZS") => "QZSST",
+        Some("IRN") => "IRNWT",
+        Some("GLO") => "GLONASST",
+        Some("UTC") => "UTC",
+        Some("TAI") => "TAI",
+        _ => "GPST",
+    }
+    .to_string()
+}
+
+// SV identifier, with the blank system of old files read as GPS ("G05")
+fn sv_name(field: &str) -> Option<String> {
+    let letter = field.chars().next()?;
+    let prn: u8 = field.get(1..)?.trim().parse().ok()?;
+    let letter = if letter == ' ' { 'G' } else { letter };
+    Some(format!("{}{:02}", letter, prn))
+}
+
+// Calendar epoch of the first line and of "*" lines ("yyyy mm dd hh mm ss.ssssssss")
+fn epoch_nanos(line: &str) -> Option<i64> {
+    let seconds = parse_f64(line, 20, 31)?;
+    Some(calendar_to_nanos(
+        parse_i32(line, 3, 7)?,
+        parse_i32(line, 8, 10)? as u8,
+        parse_i32(line, 11, 13)? as u8,
+        parse_i32(line, 14, 16)? as u8,
+        parse_i32(line, 17, 19)? as u8,
+        seconds.trunc() as u8,
+        (seconds.fract() * 1e9).round() as u32,
+    ))
+}
+
+// Same epoch, as an ISO 8601 string in the file time system
+fn epoch_string(line: &str) -> Option<String> {
+    let seconds = parse_f64(line, 20, 31)?;
+    Some(format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+        parse_i32(line, 3, 7)?,
+        parse_i32(line, 8, 10)?,
+        parse_i32(line, 11, 13)?,
+        parse_i32(line, 14, 16)?,
+        parse_i32(line, 17, 19)?,
+        seconds as u32,
+    ))
+}
+
+// Standard deviation base^exponent, scaled to SI units
+fn std_dev(line: &str, start: usize, end: usize, base: Option<f64>, scale: f64) -> Option<f64> {
+    let exponent = parse_i32(line, start, end)?;
+    Some(base?.powi(exponent) * scale)
+}
+
+fn flag(line: &str, col: usize, value: &str) -> bool {
+    line.get(col..col + 1) == Some(value)
+}
+
+impl Sp3Header {
+    fn parse_line(&mut self, line: &str) {
+        match line.get(0..2).unwrap_or("") {
+            "##" => {
+                self.gps_week = parse_i32(line, 3, 7);
+                self.seconds_of_week = parse_f64(line, 8, 23);
+                self.epoch_interval = parse_f64(line, 24, 38);
+                self.mjd = parse_i32(line, 39, 44);
+                self.fractional_day = parse_f64(line, 45, 60);
+            },
+            "+ " => {
+                for i in 0..17 {
+                    if let Some(sv) = line.get(9 + 3 * i..12 + 3 * i).and_then(sv_name) {
+                        if sv != "G00" {
+                            self.satellites.push(sv);
+                        }
+                    }
+                }
+            },
+            "++" => {
+                for i in 0..17 {
+                    if self.accuracy_exponents.len() < self.satellites.len() {
+                        self.accuracy_exponents.push(parse_i32(line, 9 + 3 * i, 12 + 3 * i));
+                    }
+                }
+            },
+            // Only the first of each pair of descriptor lines is used
+            "%c" if self.file_type.is_none() => {
+                self.file_type = field(line, 3, 5);
+                self.time_system = time_system_name(field(line, 9, 12).as_deref());
+            },
+            "%f" if self.base_pos.is_none() => {
+                self.base_pos = parse_f64(line, 3, 13).filter(|b| *b != 0.0);
+                self.base_clk = parse_f64(line, 14, 26).filter(|b| *b != 0.0);
+            },
+            "/*" => {
+                if let Some(comment) = field(line, 3, 80) {
+                    self.comments.push(comment);
+                }
+            },
+            _ => {},
+        }
+    }
+
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        dict.set_item("version", &self.version)?;
+        dict.set_item("data_type", &self.data_type)?;
+        dict.set_item("start", &self.start)?;
+        dict.set_item("num_epochs", self.num_epochs)?;
+        dict.set_item("data_used", &self.data_used)?;
+        dict.set_item("coordinate_system", &self.coordinate_system)?;
+        dict.set_item("orbit_type", &self.orbit_type)?;
+        dict.set_item("agency", &self.agency)?;
+        dict.set_item("gps_week", self.gps_week)?;
+        dict.set_item("seconds_of_week", self.seconds_of_week)?;
+        dict.set_item("epoch_interval", self.epoch_interval)?;
+        dict.set_item("mjd", self.mjd)?;
+        dict.set_item("fractional_day", self.fractional_day)?;
+        dict.set_item("file_type", &self.file_type)?;
+        dict.set_item("time_system", &self.time_system)?;
+        dict.set_item("base_pos", self.base_pos)?;
+        dict.set_item("base_clk", self.base_clk)?;
+        dict.set_item("satellites", &self.satellites)?;
+        // Accuracy of each satellite over the file, 2^exponent mm, in meters
+        let accuracy: BTreeMap<&str, Option<f64>> = self
+            .satellites
+            .iter()
+            .zip(&self.accuracy_exponents)
+            .map(|(sv, exp)| (sv.as_str(), exp.filter(|e| *e != 0).map(|e| 2f64.powi(e) * 1e-3)))
+            .collect();
+        dict.set_item("accuracy", accuracy)?;
+        dict.set_item("comments", &self.comments)?;
+        Ok(dict)
+    }
+}
+
+// Position line: "PG01" then x, y, z (km), clock (µs), standard deviation
+// exponents (mm, ps) and the clock event, clock prediction, maneuver and
+// orbit prediction flags
+fn position_record(line: &str, epoch: i64, header: &Sp3Header) -> Option<Sp3Record> {
+    let sv = sv_name(line.get(1..4)?)?;
+    let (x, y, z) = (parse_f64(line, 4, 18), parse_f64(line, 18, 32), parse_f64(line, 32, 46));
+    // Missing positions are written as zeros
+    let missing = [x, y, z].iter().all(|v| v.unwrap_or(0.0) == 0.0);
+    let km = |v: Option<f64>| if missing { None } else { v.map(|v| v * 1e3) };
+    Some(Sp3Record {
+        epoch,
+        sv,
+        x: km(x),
+        y: km(y),
+        z: km(z),
+        clock: parse_f64(line, 46, 60).filter(|c| c.abs() < BAD_CLOCK).map(|c| c * 1e-6),
+        x_std: std_dev(line, 61, 63, header.base_pos, 1e-3),
+        y_std: std_dev(line, 64, 66, header.base_pos, 1e-3),
+        z_std: std_dev(line, 67, 69, header.base_pos, 1e-3),
+        clock_std: std_dev(line, 70, 73, header.base_clk, 1e-12),
+        clock_event: flag(line, 74, "E"),
+        clock_pred: flag(line, 75, "P"),
+        maneuver: flag(line, 78, "M"),
+        orbit_pred: flag(line, 79, "P"),
+        ..Default::default()
+    })
+}
+
+// Velocity line: "VG01" then vx, vy, vz (dm/s), clock rate (10^-4 µs/s) and
+// standard deviation exponents (10^-4 mm/s, 10^-4 ps/s)
+fn add_velocity(record: &mut Sp3Record, line: &str, header: &Sp3Header) {
+    let dm = |v: Option<f64>| v.map(|v| v * 0.1);
+    record.vx = dm(parse_f64(line, 4, 18));
+    record.vy = dm(parse_f64(line, 18, 32));
+    record.vz = dm(parse_f64(line, 32, 46));
+    record.clock_rate = parse_f64(line, 46, 60)
+        .filter(|c| c.abs() < BAD_CLOCK)
+        .map(|c| c * 1e-10);
+    record.vx_std = std_dev(line, 61, 63, header.base_pos, 1e-7);
+    record.vy_std = std_dev(line, 64, 66, header.base_pos, 1e-7);
+    record.vz_std = std_dev(line, 67, 69, header.base_pos, 1e-7);
+    record.clock_rate_std = std_dev(line, 70, 73, header.base_clk, 1e-16);
+}
+
+fn parse_sp3<R: BufRead>(reader: R) -> Result<(Sp3Header, Vec<Sp3Record>), RinexError> {
+    let mut lines = reader.lines();
+    let first = lines
+        .next()
+        .transpose()
+        .map_err(|e| RinexError::parse(format!("SP3 read error: {}", e)))?
+        .unwrap_or_default();
+
+    if !first.starts_with('#') || !matches!(first.get(2..3), Some("P") | Some("V")) {
+        return Err(RinexError::wrong_type("This is not an SP3 file"));
+    }
+    let version = first.get(1..2).unwrap_or("").to_string();
+    if version != "c" && version != "d" {
+        return Err(RinexError::parse(format!("Unsupported SP3 version '{}' (expected c or d)", version)));
+    }
+
+    let mut header = Sp3Header {
+        version,
+        data_type: first[2..3].to_string(),
+        start: epoch_string(&first),
+        num_epochs: parse_i32(&first, 32, 39),
+        data_used: field(&first, 40, 45),
+        coordinate_system: field(&first, 46, 51),
+        orbit_type: field(&first, 52, 55),
+        agency: field(&first, 56, 60),
+        time_system: "GPST".to_string(),
+        ..Default::default()
+    };
+
+    let mut records: Vec<Sp3Record> = Vec::new();
+    let mut epoch: Option<i64> = None;
+    // First record of the current epoch
+    let mut epoch_start = 0;
+
+    for (index, line) in lines.enumerate() {
+        let line = line.map_err(|e| RinexError::parse(format!("SP3 read error: {}", e)))?;
+        let line_number = index + 2;
+
+        match line.chars().next() {
+            Some('*') => {
+                epoch = epoch_nanos(&line);
+                if epoch.is_none() {
+                    return Err(RinexError::corrupted_epoch(
+                        "SP3 parsing error: invalid epoch line",
+                        line_number,
+                        field(&line, 3, 31),
+                    ));
+                }
+                epoch_start = records.len();
+            },
+            Some('P') => {
+                let Some(epoch) = epoch else {
+                    continue;
+                };
+                let record = position_record(&line, epoch, &header).ok_or_else(|| {
+                    RinexError::corrupted_epoch(
+                        "SP3 parsing error: invalid position record",
+                        line_number,
+                        None,
+                    )
+                })?;
+                records.push(record);
+            },
+            Some('V') => {
+                let sv = line.get(1..4).and_then(sv_name);
+                if let Some(record) = records[epoch_start..].iter_mut().rev().find(|r| Some(&r.sv) == sv.as_ref()) {
+                    add_velocity(record, &line, &header);
+                }
+            },
+            Some('E') => {},
+            _ if epoch.is_none() => header.parse_line(&line),
+            _ => {},
+        }
+    }
+
+    Ok((header, records))
+}
+
+fn frame(records: &[Sp3Record], header: &Sp3Header) -> PyResult<DataFrame> {
+    let column = |f: fn(&Sp3Record) -> Option<f64>| records.iter().map(f).collect::<Vec<_>>();
+
+    let mut df = df![
+        "time_system" => vec![header.time_system.as_str(); records.len()],
+        "sv" => records.iter().map(|r| r.sv.as_str()).collect::<Vec<_>>(),
+        "x" => column(|r| r.x),
+        "y" => column(|r| r.y),
+        "z" => column(|r| r.z),
+        "clock" => column(|r| r.clock),
+        "x_std" => column(|r| r.x_std),
+        "y_std" => column(|r| r.y_std),
+        "z_std" => column(|r| r.z_std),
+        "clock_std" => column(|r| r.clock_std),
+        "clock_event" => records.iter().map(|r| r.clock_event).collect::<Vec<_>>(),
+        "clock_pred" => records.iter().map(|r| r.clock_pred).collect::<Vec<_>>(),
+        "maneuver" => records.iter().map(|r| r.maneuver).collect::<Vec<_>>(),
+        "orbit_pred" => records.iter().map(|r| r.orbit_pred).collect::<Vec<_>>(),
+    ]
+    .map_err(polars_error)?;
+
+    // Velocities, only in files with "V" data type
+    if header.data_type == "V" {
+        let velocities = df![
+            "vx" => column(|r| r.vx),
+            "vy" => column(|r| r.vy),
+            "vz" => column(|r| r.vz),
+            "clock_rate" => column(|r| r.clock_rate),
+            "vx_std" => column(|r| r.vx_std),
+            "vy_std" => column(|r| r.vy_std),
+            "vz_std" => column(|r| r.vz_std),
+            "clock_rate_std" => column(|r| r.clock_rate_std),
+        ]
+        .map_err(polars_error)?;
+        df.hstack_mut(velocities.get_columns())
+            .map_err(polars_error)?;
+    }
+
+    // Epoch as written in the file, in the scale given by `time_system`
+    let epochs = records.iter().map(|r| r.epoch).collect();
+    df.insert_column(0, datetime_series("epoch", epochs))
+        .map_err(polars_error)?;
+
+    Ok(df)
+}
+
+// Precise orbits (SP3-c and SP3-d, plain or gzip-compressed): one row per
+// (epoch, sv) with positions (m), clock (s), velocities (m/s, clock rate in
+// s/s) when present, standard deviations and flags; and the header
+#[pyfunction]
+pub(crate) fn read_sp3<'py>(py: Python<'py>, path: &str) -> PyResult<(PyDataFrame, Bound<'py, PyDict>)> {
+    let path = Path::new(path);
+    check_exists(path)?;
+
+    let reader = open_text_reader(path)
+        .map_err(|e| RinexError::parse(format!("SP3 read error: {}", e)).with_path(path))?;
+    let (header, records) = parse_sp3(reader).map_err(|e| e.with_path(path))?;
+
+    Ok((PyDataFrame(frame(&records, &header)?), header.to_dict(py)?))
+}
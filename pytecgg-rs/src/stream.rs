@@ -0,0 +1,295 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use pyo3_polars::PyDataFrame;
+use rinex::header::Header;
+use rinex::prelude::*;
+use std::io::{BufRead, BufReader, Cursor};
+use std::path::Path;
+use std::sync::Mutex;
+
+use crate::error::RinexError;
+use crate::filter::ObsFilter;
+use crate::header::{obs_header_dict, open_text_reader, parse_header_text, HeaderExtras};
+use crate::obs::{check_exists, obs_long_frame, obs_wide_frame};
+use crate::scan::{is_end_of_header, is_epoch_start, locate_corrupted, parse_lenient, SkippedEpoch};
+use crate::time::parse_timescale;
+
+fn io_error(e: std::io::Error) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("RINEX read error: {}", e))
+}
+
+// Iterator over an observation file, yielding one DataFrame every `chunk_size`
+// epochs. Only the header and the current chunk are held in memory: each chunk
+// is parsed on its own, behind a copy of the file header.
+#[pyclass(module = "pytecggrs")]
+pub(crate) struct RinexObsReader {
+    reader: Mutex<Box<dyn BufRead + Send>>,
+    path: String,
+    header_text: String,
+    header: Header,
+    header_extras: HeaderExtras,
+    is_v2: bool,
+    chunk_size: usize,
+    wide: bool,
+    timescale: Option<TimeScale>,
+    filter: ObsFilter,
+    lenient: bool,
+    // Epochs dropped so far in lenient mode, with line numbers of the file
+    skipped: Vec<SkippedEpoch>,
+    // First line of the next chunk, already consumed from the reader
+    pending: Option<String>,
+    exhausted: bool,
+    // Number of lines consumed from the reader so far, and line number (in
+    // the file) of the first epoch of the current chunk
+    lines_read: usize,
+    chunk_line: usize,
+}
+
+impl RinexObsReader {
+    // Raw text (header included) of the next `chunk_size` epochs
+    fn next_chunk(&mut self) -> PyResult<Option<String>> {
+        if self.exhausted && self.pending.is_none() {
+            return Ok(None);
+        }
+
+        let mut chunk = self.header_text.clone();
+        let mut n_epochs = 0;
+        self.chunk_line = self.lines_read + 1;
+        if let Some(line) = self.pending.take() {
+            self.chunk_line = self.lines_read;
+            chunk.push_str(&line);
+            n_epochs += 1;
+        }
+
+        let reader = self.reader.get_mut().map_err(|_| {
+            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("RINEX reader is poisoned")
+        })?;
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line).map_err(io_error)? == 0 {
+                self.exhausted = true;
+                break;
+            }
+            self.lines_read += 1;
+            if is_epoch_start(&line, self.is_v2) {
+                if n_epochs == self.chunk_size {
+                    self.pending = Some(line);
+                    break;
+                }
+                n_epochs += 1;
+            }
+            chunk.push_str(&line);
+        }
+
+        Ok(if n_epochs == 0 { None } else { Some(chunk) })
+    }
+
+    // Parses the current chunk. Corrupted epochs are located with line numbers
+    // of the file rather than of the chunk: in lenient mode they are dropped
+    // and recorded, otherwise the first one is raised.
+    fn parse_chunk(&mut self, chunk: &str) -> Result<Rinex, RinexError> {
+        let reason = match Rinex::from_reader(&mut BufReader::new(Cursor::new(chunk.as_bytes()))) {
+            Ok(rinex) => return Ok(rinex),
+            Err(e) => e,
+        };
+
+        let first_line = self.chunk_line;
+        let header_lines = self.header_text.lines().count();
+        let to_file_line = |line: usize| first_line + line - header_lines - 1;
+
+        let Some((rinex, skipped)) = self.lenient.then(|| parse_lenient(chunk)).flatten() else {
+            return Err(match locate_corrupted(chunk) {
+                Some(error) => RinexError {
+                    line: error.line.map(to_file_line),
+                    ..error.with_path(Path::new(&self.path))
+                },
+                None => self.chunk_error(reason),
+            });
+        };
+        let skipped: Vec<SkippedEpoch> = skipped
+            .into_iter()
+            .map(|s| SkippedEpoch {
+                start_line: to_file_line(s.start_line),
+                end_line: to_file_line(s.end_line),
+                ..s
+            })
+            .collect();
+        self.skipped.extend(skipped);
+        Ok(rinex)
+    }
+
+    // Parsing failure of the current chunk, located by its first line
+    fn chunk_error(&self, reason: impl std::fmt::Display) -> RinexError {
+        RinexError::parse(format!(
+            "RINEX parsing error in the chunk starting at line {}: {}",
+            self.chunk_line, reason
+        ))
+        .with_path(Path::new(&self.path))
+    }
+}
+
+#[pymethods]
+impl RinexObsReader {
+    #[new]
+    #[pyo3(signature = (
+        path,
+        chunk_size=120,
+        wide=false,
+        timescale=None,
+        constellations=None,
+        svs=None,
+        observables=None,
+        observable_regex=None,
+        start=None,
+        end=None,
+        interval=None,
+        lenient=false,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        path: &str,
+        chunk_size: usize,
+        wide: bool,
+        timescale: Option<&str>,
+        constellations: Option<Vec<String>>,
+        svs: Option<Vec<String>>,
+        observables: Option<Vec<String>>,
+        observable_regex: Option<&str>,
+        start: Option<&str>,
+        end: Option<&str>,
+        interval: Option<f64>,
+        lenient: bool,
+    ) -> PyResult<Self> {
+        let path_str = path.to_string();
+        let path = Path::new(path);
+        if chunk_size == 0 {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "chunk_size must be a positive number of epochs",
+            ));
+        }
+        check_exists(path)?;
+
+        let timescale = timescale.map(parse_timescale).transpose()?;
+        let filter = ObsFilter::new(
+            constellations,
+            svs,
+            observables,
+            observable_regex,
+            start,
+            end,
+            interval,
+        )?;
+
+        let mut reader = open_text_reader(path).map_err(io_error)?;
+
+        let mut header_text = String::new();
+        let mut lines_read = 0;
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line).map_err(io_error)? == 0 {
+                return Err(RinexError::parse("RINEX header is missing END OF HEADER")
+                    .with_path(path)
+                    .into());
+            }
+            lines_read += 1;
+            header_text.push_str(&line);
+            if is_end_of_header(&line) {
+                break;
+            }
+        }
+
+        let first_line = header_text.lines().next().unwrap_or("");
+        if first_line.get(60..).is_some_and(|label| label.trim().starts_with("CRINEX")) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "Hatanaka-compressed files cannot be streamed, decompress them first",
+            ));
+        }
+        if first_line.get(20..21) != Some("O") {
+            return Err(RinexError::wrong_type("This is not a RINEX Observation file")
+                .with_path(path)
+                .into());
+        }
+
+        let header = parse_header_text(&header_text)
+            .map_err(|e| e.with_path(path))?
+            .header;
+        let header_extras = HeaderExtras::from_reader(Cursor::new(header_text.as_bytes()))
+            .map_err(io_error)?;
+        let is_v2 = header.version.major < 3;
+
+        Ok(Self {
+            reader: Mutex::new(reader),
+            path: path_str,
+            header_text,
+            header,
+            header_extras,
+            is_v2,
+            chunk_size,
+            wide,
+            timescale,
+            filter,
+            lenient,
+            skipped: Vec::new(),
+            pending: None,
+            exhausted: false,
+            lines_read,
+            chunk_line: lines_read + 1,
+        })
+    }
+
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(mut slf: PyRefMut<'_, Self>) -> PyResult<Option<PyDataFrame>> {
+        // Chunks whose epochs are all filtered out are skipped
+        while let Some(chunk) = slf.next_chunk()? {
+            let rinex = slf.parse_chunk(&chunk)?;
+
+            // Epochs are sorted: once past the end of the time window, the
+            // rest of the file is left unread
+            let past_end = match &rinex.record {
+                Record::ObsRecord(obs_data) => obs_data
+                    .keys()
+                    .next_back()
+                    .is_some_and(|obs_key| slf.filter.past_end(&obs_key.epoch)),
+                _ => false,
+            };
+            if past_end {
+                slf.exhausted = true;
+                slf.pending = None;
+            }
+
+            let df = if slf.wide {
+                obs_wide_frame(&rinex, slf.timescale, &slf.filter)?
+            } else {
+                obs_long_frame(&rinex, slf.timescale, &slf.filter)?
+            };
+            if df.height() > 0 {
+                return Ok(Some(PyDataFrame(df)));
+            }
+        }
+        Ok(None)
+    }
+
+    #[getter]
+    fn header<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        obs_header_dict(py, &self.header, &self.header_extras)
+    }
+
+    // Epochs dropped so far in lenient mode
+    #[getter]
+    fn skipped_epochs<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        self.skipped.iter().map(|s| s.to_dict(py)).collect()
+    }
+
+    #[getter]
+    fn rx_position(&self) -> (f64, f64, f64) {
+        self.header.rx_position.unwrap_or((f64::NAN, f64::NAN, f64::NAN))
+    }
+
+    #[getter]
+    fn version(&self) -> String {
+        self.header.version.to_string()
+    }
+}
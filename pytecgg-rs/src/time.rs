@@ -0,0 +1,112 @@
+use polars::prelude::*;
+use pyo3::prelude::*;
+use rinex::prelude::*;
+
+pub(crate) fn parse_timescale(name: &str) -> PyResult<TimeScale> {
+    match name.trim().to_uppercase().as_str() {
+        "GPST" | "GPS" => Ok(TimeScale::GPST),
+        "UTC" => Ok(TimeScale::UTC),
+        "TAI" => Ok(TimeScale::TAI),
+        _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            format!("Unsupported time scale: {} (expected one of GPST, UTC, TAI)", name)
+        )),
+    }
+}
+
+// Days since 1970-01-01 of a proleptic Gregorian date (H. Hinnant's algorithm)
+fn days_from_civil(year: i32, month: u8, day: u8) -> i64 {
+    let y = if month <= 2 { year as i64 - 1 } else { year as i64 };
+    let era = (if y >= 0 { y } else { y - 399 }) / 400;
+    let yoe = y - era * 400;
+    let m = month as i64;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+// Proleptic Gregorian date of a number of days since 1970-01-01 (inverse of
+// `days_from_civil`)
+fn civil_from_days(days: i64) -> (i32, u8, u8) {
+    let z = days + 719_468;
+    let era = (if z >= 0 { z } else { z - 146_096 }) / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year as i32, month as u8, day as u8)
+}
+
+// Calendar date and time (whole seconds) of nanoseconds since
+// 1970-01-01T00:00:00, the inverse of `calendar_to_nanos`
+pub(crate) fn nanos_to_calendar(nanos: i64) -> (i32, u8, u8, u8, u8, u8) {
+    let secs = nanos.div_euclid(1_000_000_000);
+    let (y, m, d) = civil_from_days(secs.div_euclid(86_400));
+    let sod = secs.rem_euclid(86_400);
+    (y, m, d, (sod / 3_600) as u8, (sod % 3_600 / 60) as u8, (sod % 60) as u8)
+}
+
+// Nanoseconds since 1970-01-01T00:00:00 of the calendar representation of
+// `epoch` in time scale `ts` (leap seconds are handled by hifitime)
+pub(crate) fn epoch_to_nanos(epoch: &Epoch, ts: TimeScale) -> i64 {
+    let (y, m, d, hh, mm, ss, ns) = epoch.to_gregorian(ts);
+    calendar_to_nanos(y, m, d, hh, mm, ss, ns)
+}
+
+// Nanoseconds since 1970-01-01T00:00:00 of a calendar date, as written in a
+// file (no time scale conversion)
+pub(crate) fn calendar_to_nanos(y: i32, m: u8, d: u8, hh: u8, mm: u8, ss: u8, ns: u32) -> i64 {
+    let secs = days_from_civil(y, m, d) * 86_400
+        + hh as i64 * 3_600
+        + mm as i64 * 60
+        + ss as i64;
+    secs * 1_000_000_000 + ns as i64
+}
+
+// Builds a Datetime(ns, UTC) series out of nanosecond timestamps
+pub(crate) fn datetime_series(name: &str, nanos: Vec<i64>) -> Series {
+    Int64Chunked::from_vec(name.into(), nanos)
+        .into_datetime(TimeUnit::Nanoseconds, Some(TimeZone::UTC))
+        .into_series()
+}
+
+// Same as `datetime_series`, with missing timestamps
+pub(crate) fn datetime_series_opt(name: &str, nanos: Vec<Option<i64>>) -> Series {
+    Int64Chunked::from_iter_options(name.into(), nanos.into_iter())
+        .into_datetime(TimeUnit::Nanoseconds, Some(TimeZone::UTC))
+        .into_series()
+}
+
+// Start of week 0 (seconds since 1970-01-01) of a GNSS time scale: BDT weeks
+// start on 2006-01-01, GST/QZSST/IRNWT weeks are aligned to GPS ones
+pub(crate) fn week_origin_s(time_system: &str) -> i64 {
+    match time_system {
+        "BDT" => days_from_civil(2006, 1, 1) * 86_400,
+        _ => days_from_civil(1980, 1, 6) * 86_400,
+    }
+}
+
+// Offset (ns) to add to a calendar timestamp in `time_system` to express it in
+// GPST. GST, QZSST and IRNWT are aligned to GPST; GLONASST is UTC(SU) + 3 h.
+pub(crate) fn gpst_offset_ns(time_system: &str, nanos: i64) -> PyResult<i64> {
+    let utc_offset_ns = |nanos: i64| {
+        let leap = Epoch::from_unix_seconds(nanos as f64 * 1e-9).leap_seconds_iers() as i64;
+        (leap - 19) * 1_000_000_000
+    };
+    match time_system {
+        "GPST" | "GST" | "QZSST" | "IRNWT" => Ok(0),
+        "BDT" => Ok(14_000_000_000),
+        "TAI" => Ok(-19_000_000_000),
+        "UTC" => Ok(utc_offset_ns(nanos)),
+        "GLONASST" => {
+            let utc = nanos - 3 * 3_600 * 1_000_000_000;
+            Ok(utc_offset_ns(utc) - 3 * 3_600 * 1_000_000_000)
+        },
+        _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Unsupported time system: {} (expected one of GPST, GST, QZSST, IRNWT, BDT, GLONASST, UTC, TAI)",
+            time_system
+        ))),
+    }
+}
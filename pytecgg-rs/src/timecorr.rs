@@ -0,0 +1,141 @@
+use polars::prelude::*;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use pyo3_polars::PyDataFrame;
+use std::path::Path;
+
+use crate::error::polars_error;
+use crate::header::{field, parse_f64, parse_i32};
+use crate::scan::NavText;
+use crate::time::datetime_series_opt;
+
+// One broadcast time system correction (e.g. GPUT: GPS to UTC), from a
+// header record or a RINEX 4 STO message
+#[derive(Default)]
+struct TimeCorrection {
+    source: &'static str,
+    kind: String,
+    sv: Option<String>,
+    msg_type: Option<String>,
+    epoch: Option<i64>,
+    ref_time: Option<i32>,
+    ref_week: Option<i32>,
+    t_tm: Option<f64>,
+    a0: Option<f64>,
+    a1: Option<f64>,
+    a2: Option<f64>,
+    sbas_id: Option<String>,
+    utc_id: Option<String>,
+}
+
+// RINEX 3 "TIME SYSTEM CORR" and RINEX 2 "DELTA-UTC: A0,A1,T,W" records
+fn header_corrections(nav: &NavText) -> Vec<TimeCorrection> {
+    let mut corrections = Vec::new();
+    for line in nav.records("TIME SYSTEM CORR") {
+        let Some(kind) = field(line, 0, 4) else {
+            continue;
+        };
+        corrections.push(TimeCorrection {
+            source: "header",
+            kind,
+            a0: parse_f64(line, 5, 22),
+            a1: parse_f64(line, 22, 38),
+            ref_time: parse_i32(line, 38, 45),
+            ref_week: parse_i32(line, 45, 50),
+            sbas_id: field(line, 51, 56),
+            utc_id: field(line, 57, 59),
+            ..Default::default()
+        });
+    }
+    for line in nav.records("DELTA-UTC: A0,A1,T,W") {
+        corrections.push(TimeCorrection {
+            source: "header",
+            kind: "GPUT".to_string(),
+            a0: parse_f64(line, 3, 22),
+            a1: parse_f64(line, 22, 41),
+            ref_time: parse_i32(line, 41, 50),
+            ref_week: parse_i32(line, 50, 59),
+            ..Default::default()
+        });
+    }
+    corrections
+}
+
+// RINEX 4 STO messages: reference epoch, correction type, SBAS and UTC
+// identifiers, then t_tm and the A0-A2 polynomial
+fn message_corrections(nav: &NavText) -> Vec<TimeCorrection> {
+    nav.messages("STO")
+        .map(|message| {
+            let first = message.lines.first().map(String::as_str).unwrap_or("");
+            let second = message.lines.get(1).map(String::as_str).unwrap_or("");
+            TimeCorrection {
+                source: "message",
+                kind: field(first, 23, 42).unwrap_or_default(),
+                sv: Some(message.sv.clone()),
+                msg_type: Some(message.msg_type.clone()),
+                epoch: message.epoch_nanos(),
+                t_tm: parse_f64(second, 4, 23),
+                a0: parse_f64(second, 23, 42),
+                a1: parse_f64(second, 42, 61),
+                a2: parse_f64(second, 61, 80),
+                sbas_id: field(first, 43, 61),
+                utc_id: field(first, 62, 80),
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+// RINEX 3 "LEAP SECONDS" record (RINEX 2 only has the current value)
+fn leap_seconds<'py>(py: Python<'py>, nav: &NavText) -> PyResult<Option<Bound<'py, PyDict>>> {
+    let Some(line) = nav.records("LEAP SECONDS").next() else {
+        return Ok(None);
+    };
+    let dict = PyDict::new(py);
+    dict.set_item("leap_seconds", parse_i32(line, 0, 6))?;
+    dict.set_item("future_leap_seconds", parse_i32(line, 6, 12))?;
+    dict.set_item("week", parse_i32(line, 12, 18))?;
+    dict.set_item("day", parse_i32(line, 18, 24))?;
+    dict.set_item("time_system", field(line, 24, 27).unwrap_or_else(|| "GPS".to_string()))?;
+    Ok(Some(dict))
+}
+
+fn frame(corrections: &[TimeCorrection]) -> PyResult<DataFrame> {
+    let mut df = df![
+        "source" => corrections.iter().map(|c| c.source).collect::<Vec<_>>(),
+        "type" => corrections.iter().map(|c| c.kind.as_str()).collect::<Vec<_>>(),
+        "sv" => corrections.iter().map(|c| c.sv.as_deref()).collect::<Vec<_>>(),
+        "msg_type" => corrections.iter().map(|c| c.msg_type.as_deref()).collect::<Vec<_>>(),
+        "ref_week" => corrections.iter().map(|c| c.ref_week).collect::<Vec<_>>(),
+        "ref_time" => corrections.iter().map(|c| c.ref_time).collect::<Vec<_>>(),
+        "t_tm" => corrections.iter().map(|c| c.t_tm).collect::<Vec<_>>(),
+        "a0" => corrections.iter().map(|c| c.a0).collect::<Vec<_>>(),
+        "a1" => corrections.iter().map(|c| c.a1).collect::<Vec<_>>(),
+        "a2" => corrections.iter().map(|c| c.a2).collect::<Vec<_>>(),
+        "sbas_id" => corrections.iter().map(|c| c.sbas_id.as_deref()).collect::<Vec<_>>(),
+        "utc_id" => corrections.iter().map(|c| c.utc_id.as_deref()).collect::<Vec<_>>(),
+    ]
+    .map_err(polars_error)?;
+
+    // Reference epoch of RINEX 4 messages, as written in the file
+    let epochs = corrections.iter().map(|c| c.epoch).collect();
+    df.insert_column(4, datetime_series_opt("epoch", epochs))
+        .map_err(polars_error)?;
+
+    Ok(df)
+}
+
+// Broadcast time system corrections (GPUT, GAUT, BDUT, GLUT, GPGA...) from
+// header records and RINEX 4 STO messages, and the LEAP SECONDS record
+#[pyfunction]
+pub(crate) fn read_rinex_nav_time<'py>(
+    py: Python<'py>,
+    path: &str,
+) -> PyResult<(PyDataFrame, Option<Bound<'py, PyDict>>)> {
+    let nav = NavText::from_path(Path::new(path))?;
+
+    let mut corrections = header_corrections(&nav);
+    corrections.extend(message_corrections(&nav));
+
+    Ok((PyDataFrame(frame(&corrections)?), leap_seconds(py, &nav)?))
+}
@@ -52,7 +52,7 @@ def process_observations(
     Args:
         obs_data (pl.DataFrame): DataFrame containing observation data
         system (Literal["G", "E", "C", "R"]): GNSS system identifier
-        glonass_freq (Optional[dict[str, int]]): Frequency mapping for GLONASS, required if system is "R"
+        glonass_freq (Optional[dict[str, int]]): Slot to frequency channel mapping for GLONASS (e.g. {"R01": 1}), required if system is "R"
 
     Returns:
         pl.DataFrame: DataFrame with calculated GFLC values
@@ -81,11 +81,15 @@ def process_observations(
 
     # Frequency handling
     if system == "R":
-        # FIXME
         if glonass_freq is None:
-            raise ValueError("glonass_freq is required for GLONASS processing")
+            raise ValueError(
+                "glonass_freq is required for GLONASS processing "
+                "(see pytecggrs.read_glonass_channels)"
+            )
         df_pivot = df_pivot.with_columns(
-            pl.col("sv").map_dict(glonass_freq).alias("freq_number")
+            pl.col("sv")
+            .replace_strict(glonass_freq, default=None, return_dtype=pl.Int8)
+            .alias("freq_number")
         )
         freq1 = (1602 + pl.col("freq_number") * 0.5625) * 1e6
         freq2 = (1246 + pl.col("freq_number") * 0.4375) * 1e6
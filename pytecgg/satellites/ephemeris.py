@@ -6,18 +6,27 @@ import polars as pl
 from pytecgg.satellites import CONSTELLATION_PARAMS
 
 
-def _parse_time(time_str: str, time_system: str, time_offset: timedelta) -> datetime:
+def _parse_time(
+    time_str: str | datetime, time_system: str, time_offset: timedelta
+) -> datetime:
     """
     Parse RINEX time string with time system awareness
 
     Parameters:
-    time_str (str): Time string from RINEX file
+    time_str (str | datetime): Time string from RINEX file, or toc datetime from `read_rinex_nav`
     time_system (str): Time system identifier ('GPST', 'BDT', etc.)
     time_offset (timedelta): Offset to apply for conversion to UTC
 
     Returns:
     datetime: Timezone-aware datetime in UTC
     """
+    if isinstance(time_str, datetime):
+        # Datetime columns are labelled UTC, but expressed in `time_system`
+        dt = time_str.replace(tzinfo=None)
+        if time_offset:
+            dt = dt - time_offset
+        return dt.replace(tzinfo=timezone.utc)
+
     if isinstance(time_str, str):
         # Remove time system suffix if present
         clean_str = time_str.split(f" {time_system}")[0].strip()
@@ -58,7 +67,7 @@ def _greg2gps(dt: datetime) -> tuple[int, float]:
 
 
 def prepare_ephemeris(
-    nav: dict[str, pl.DataFrame], constellation: str
+    nav: dict[str, pl.DataFrame], constellation: str, msg_type: str | None = None
 ) -> dict[str, dict[str, Any]]:
     """
     Prepare ephemeris data for specified constellation (GPS, BeiDou, etc.)
@@ -66,6 +75,7 @@ def prepare_ephemeris(
     Parameters:
     nav (dict): Dictionary of DataFrames containing navigation data from RINEX file (keyed by constellation)
     constellation (str): Constellation name ('GPS', 'BeiDou', etc.)
+    msg_type (str, optional): Navigation message to use ('LNAV', 'INAV', 'FNAV', 'D1', ...), all by default
 
     Returns:
     dict: Dictionary with prepared ephemeris data for each satellite
@@ -76,8 +86,12 @@ def prepare_ephemeris(
     params = CONSTELLATION_PARAMS[constellation]
     ephem_dict = {}
 
-    for sat in nav[constellation]["sv"].unique().to_list():
-        sat_data = nav[constellation].filter(pl.col("sv") == sat)
+    nav_data = nav[constellation]
+    if msg_type is not None:
+        nav_data = nav_data.filter(pl.col("msg_type") == msg_type.upper())
+
+    for sat in nav_data["sv"].unique().to_list():
+        sat_data = nav_data.filter(pl.col("sv") == sat)
         if sat_data.is_empty():
             continue
 
@@ -85,7 +99,7 @@ def prepare_ephemeris(
         ephe_row = sat_data[len(sat_data) // 2]
 
         ephe_time = _parse_time(
-            ephe_row["epoch"].item(), params.time_system, params.time_offset
+            ephe_row["toc"].item(), params.time_system, params.time_offset
         )
         gps_week, gps_sec = _greg2gps(ephe_time)
 
@@ -103,6 +117,6 @@ def prepare_ephemeris(
             },
         }
 
-        ephem_dict[f"{params.prefix}{int(sat):02d}"] = ephem
+        ephem_dict[sat] = ephem
 
     return ephem_dict
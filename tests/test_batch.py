@@ -0,0 +1,104 @@
+from pytecggrs import read_rinex_nav_many, read_rinex_obs, read_rinex_obs_many
+from polars import DataFrame
+
+
+def test_read_many_concat(obs_v2_file, obs_v3_file):
+    """Check that several files are read into one frame tagged by station"""
+    df, errors = read_rinex_obs_many([obs_v2_file, obs_v3_file])
+    assert errors == {}
+    assert isinstance(df, DataFrame)
+    assert df.columns[0] == "station"
+    assert set(df["station"].unique().to_list()) == {"CGTC", "ASIR00ITA"}
+
+    df_v2, _, _ = read_rinex_obs(obs_v2_file)
+    df_v3, _, _ = read_rinex_obs(obs_v3_file)
+    assert df.height == df_v2.height + df_v3.height
+
+
+def test_read_many_by_station(obs_v2_file, obs_v3_file):
+    """Check that frames can be returned keyed by station"""
+    frames, errors = read_rinex_obs_many(
+        [obs_v2_file, obs_v3_file], concat=False, n_threads=2
+    )
+    assert errors == {}
+    assert set(frames) == {"CGTC", "ASIR00ITA"}
+    assert all(isinstance(df, DataFrame) for df in frames.values())
+
+
+def test_read_many_glob_with_filter(test_data_dir):
+    """Check glob expansion and filtering of a batch"""
+    df, errors = read_rinex_obs_many(
+        str(test_data_dir / "v3" / "obs" / "*.rnx"), constellations=["G"]
+    )
+    assert errors == {}
+    assert df["station"].n_unique() == 2
+    assert df["sv"].str.starts_with("G").all()
+
+
+def test_read_many_collects_errors(obs_v3_file, invalid_file, nav_v3_file):
+    """Check that per-file errors are collected rather than aborting the batch"""
+    df, errors = read_rinex_obs_many([obs_v3_file, invalid_file, nav_v3_file])
+    assert set(errors) == {invalid_file, nav_v3_file}
+    assert df["station"].unique().to_list() == ["ASIR00ITA"]
+
+
+def test_read_nav_many_deduplicates(nav_v3_file):
+    """Check that records found in several navigation files are kept once"""
+    single, _, _ = read_rinex_nav_many([nav_v3_file])
+    merged, _, _ = read_rinex_nav_many([nav_v3_file, nav_v3_file])
+
+    assert set(merged) == set(single)
+    for constellation, df in merged.items():
+        assert df.height == single[constellation].height
+        assert df.columns[:6] == ["row_id", "toc", "time_system", "sv", "msg_type", "source"]
+        assert df["toc"].is_sorted()
+
+
+def test_read_nav_many_conflicts(nav_v3_file, tmp_path):
+    """Check that conflicting records are resolved in favour of the first file"""
+    with open(nav_v3_file) as f:
+        content = f.read()
+    modified = tmp_path / "BRDC_modified.rnx"
+    modified.write_text(content.replace("8.474473142996e-04", "8.474473142997e-04", 1))
+
+    merged, conflicts, _ = read_rinex_nav_many([nav_v3_file, str(modified)])
+    conflicts = [c for c in conflicts if c["dropped"] == str(modified)]
+
+    assert len(conflicts) == 1
+    assert conflicts[0]["sv"] == "C02"
+    assert conflicts[0]["kept"] == nav_v3_file
+    assert str(modified) not in merged["BeiDou"].filter(sv="C02")["source"].to_list()
+
+
+def test_read_nav_many_collects_errors(nav_v3_file, obs_v3_file, invalid_file):
+    """Check that unreadable navigation files are reported without aborting the merge"""
+    single, single_conflicts, _ = read_rinex_nav_many([nav_v3_file])
+    merged, conflicts, errors = read_rinex_nav_many([nav_v3_file, invalid_file, obs_v3_file])
+
+    assert set(errors) == {invalid_file, obs_v3_file}
+    assert len(conflicts) == len(single_conflicts)
+    for constellation, df in merged.items():
+        assert df.height == single[constellation].height
+
+
+def test_read_nav_many_galileo_data_sources(nav_v3_file, tmp_path):
+    """Check that the E1-B and E5b copies of a Galileo I/NAV ephemeris are not conflicts"""
+    with open(nav_v3_file) as f:
+        lines = f.readlines()
+    header = lines[: next(i for i, line in enumerate(lines) if "END OF HEADER" in line) + 1]
+    start = next(
+        i for i, line in enumerate(lines) if line.startswith("E") and "5.170000000000e+02" in lines[i + 5]
+    )
+    record = "".join(lines[start : start + 8])
+
+    paths = []
+    for source in ("5.130000000000e+02", "5.160000000000e+02"):
+        path = tmp_path / f"BRDC_{source[:4]}.rnx"
+        path.write_text("".join(header) + record.replace("5.170000000000e+02", source))
+        paths.append(str(path))
+
+    merged, conflicts, errors = read_rinex_nav_many(paths)
+
+    assert errors == {}
+    assert conflicts == []
+    assert sorted(merged["Galileo"]["dataSrc"].to_list()) == [513.0, 516.0]
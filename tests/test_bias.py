@@ -0,0 +1,144 @@
+from datetime import datetime, timezone
+
+from pytecggrs import RinexParseError, WrongRinexTypeError, read_bias_sinex, read_code_dcb
+import pytest
+
+
+def _bias(kind, svn, prn, station, obs1, obs2, start, end, value, std):
+    return (
+        f" {kind:<4} {svn:<4} {prn:<3} {station:<9} {obs1:<4} {obs2:<4} {start} {end} {'ns':<4}"
+        f" {value:>21.4f} {std:>11.4f}\n"
+    )
+
+
+def _sinex(records):
+    return (
+        "%=BIA 1.00 COD 2024:290:30527 IGS 2024:281:00000 2024:282:00000 A 00000003\n"
+        "+FILE/REFERENCE\n"
+        f" {'DESCRIPTION':<18} CODE, Astronomical Institute, University of Bern\n"
+        f" {'SOFTWARE':<18} Bernese GNSS Software Version 5.3\n"
+        "-FILE/REFERENCE\n"
+        "+FILE/COMMENT\n"
+        " Test biases\n"
+        "-FILE/COMMENT\n"
+        "+BIAS/DESCRIPTION\n"
+        f" {'BIAS_MODE':<39}ABSOLUTE\n"
+        f" {'TIME_SYSTEM':<39}G\n"
+        "-BIAS/DESCRIPTION\n"
+        "+BIAS/SOLUTION\n"
+        "*BIAS SVN_ PRN STATION__ OBS1 OBS2 BIAS_START____ BIAS_END______ UNIT __ESTIMATED_VALUE____ _STD_DEV___\n"
+        + records
+        + "-BIAS/SOLUTION\n"
+        "%=ENDBIA\n"
+    )
+
+
+def test_read_bias_sinex(tmp_path):
+    """Check satellite OSB/DSB and receiver biases of a Bias-SINEX file"""
+    records = (
+        _bias("OSB", "G063", "G01", "", "C1C", "", "2024:281:00000", "2024:282:00000", 10.2472, 0.0062)
+        + _bias("DSB", "G063", "G01", "", "C1C", "C1W", "2024:281:00000", "2024:282:00000", -0.9241, 0.0035)
+        + _bias("DSB", "", "G", "ALBH00CAN", "C1C", "C2W", "2024:281:43200", "0000:000:00000", 3.5, 0.1)
+    )
+    path = tmp_path / "COD0OPSFIN_20242810000_01D_01D_OSB.BIA"
+    path.write_text(_sinex(records))
+
+    df, header = read_bias_sinex(str(path))
+
+    assert df.columns == [
+        "bias_type", "svn", "sv", "station", "domes", "system", "obs1", "obs2",
+        "start", "end", "time_system", "unit", "bias", "bias_std",
+    ]
+    assert df["bias_type"].to_list() == ["OSB", "DSB", "DSB"]
+    assert df["sv"].to_list() == ["G01", "G01", None]
+    assert df["station"].to_list() == [None, None, "ALBH00CAN"]
+    assert df["domes"].to_list() == [None, None, None]
+    assert df["system"].to_list() == ["G", "G", "G"]
+    assert df["obs2"].to_list() == [None, "C1W", "C2W"]
+    assert df["start"][2] == datetime(2024, 10, 7, 12, tzinfo=timezone.utc)
+    assert df["end"].to_list() == [datetime(2024, 10, 8, tzinfo=timezone.utc)] * 2 + [None]
+    assert df["bias"].to_list() == pytest.approx([10.2472, -0.9241, 3.5])
+    assert df["bias_std"].to_list() == pytest.approx([0.0062, 0.0035, 0.1])
+
+    assert header["version"] == "1.00"
+    assert header["start"] == "2024-10-07T00:00:00"
+    assert header["time_system"] == "GPST"
+    assert header["description"]["BIAS_MODE"] == "ABSOLUTE"
+    assert header["reference"] == {
+        "DESCRIPTION": "CODE, Astronomical Institute, University of Bern",
+        "SOFTWARE": "Bernese GNSS Software Version 5.3",
+    }
+    assert header["comments"] == ["Test biases"]
+
+
+def test_read_bias_sinex_errors(tmp_path, nav_v3_file):
+    """Check errors for other files and corrupted bias records"""
+    with pytest.raises(WrongRinexTypeError):
+        read_bias_sinex(nav_v3_file)
+
+    path = tmp_path / "broken.bia"
+    path.write_text(_sinex(_bias("OSB", "G063", "G01", "", "C1C", "", "2024:2x1:00000", "2024:282:00000", 1.0, 0.1)))
+    with pytest.raises(RinexParseError) as excinfo:
+        read_bias_sinex(str(path))
+    assert excinfo.value.line == 15
+    assert excinfo.type is RinexParseError
+    assert excinfo.value.epoch is None
+
+
+def _dcb(title, records):
+    return (
+        f"{title}\n"
+        "--------------------------------------------------------------------------------\n"
+        "\n"
+        "DIFFERENTIAL CODE BIASES FOR SATELLITES AND RECEIVERS:\n"
+        "\n"
+        "PRN / STATION NAME        VALUE (NS)  RMS (NS)\n"
+        "***   ****************    *****.***   *****.***\n"
+        + records
+    )
+
+
+def test_read_code_dcb(tmp_path):
+    """Check satellite and receiver biases and the window of a monthly CODE DCB file"""
+    records = (
+        "G01                          -0.123      0.005\n"
+        "R01                           0.456      0.010\n"
+        "G    ALBH 40129M003          -2.080      0.015\n"
+    )
+    path = tmp_path / "P1C12410.DCB"
+    path.write_text(_dcb("CODE'S MONTHLY GNSS P1-C1 DCB SOLUTION, YEAR 2024, MONTH 10          08-NOV-24 07:54", records))
+
+    df, header = read_code_dcb(str(path))
+
+    assert df["bias_type"].to_list() == ["DSB"] * 3
+    assert df["sv"].to_list() == ["G01", "R01", None]
+    assert df["station"].to_list() == [None, None, "ALBH"]
+    assert df["domes"].to_list() == [None, None, "40129M003"]
+    assert df["system"].to_list() == ["G", "R", "G"]
+    # P1-C1 as RINEX 3 codes
+    assert df["obs1"].to_list() == ["C1W", "C1P", "C1W"]
+    assert df["obs2"].to_list() == ["C1C", "C1C", "C1C"]
+    assert df["bias"].to_list() == pytest.approx([-0.123, 0.456, -2.080])
+    assert df["bias_std"].to_list() == pytest.approx([0.005, 0.010, 0.015])
+    assert df["start"].unique().to_list() == [datetime(2024, 10, 1, tzinfo=timezone.utc)]
+    assert df["end"].unique().to_list() == [datetime(2024, 11, 1, tzinfo=timezone.utc)]
+    assert header["observables"] == "P1-C1"
+
+
+def test_read_code_dcb_30_day(tmp_path, nav_v3_file):
+    """Check the window of a 30-day solution and the rejection of other files"""
+    path = tmp_path / "P1P2.DCB"
+    path.write_text(_dcb(
+        "CODE'S 30-DAY GNSS P1-P2 DCB SOLUTION, ENDING DAY 281, 2024          08-OCT-24 07:30",
+        "G01                          -7.123      0.012\n",
+    ))
+
+    df, header = read_code_dcb(str(path))
+
+    assert df.row(0, named=True)["obs1"] == "C1W"
+    assert df.row(0, named=True)["obs2"] == "C2W"
+    assert header["start"] == "2024-09-08T00:00:00"
+    assert header["end"] == "2024-10-08T00:00:00"
+
+    with pytest.raises(WrongRinexTypeError):
+        read_code_dcb(nav_v3_file)
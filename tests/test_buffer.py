@@ -0,0 +1,82 @@
+import gzip
+import io
+
+from pytecggrs import (
+    read_rinex_nav,
+    read_rinex_nav_bytes,
+    read_rinex_obs,
+    read_rinex_obs_bytes,
+)
+import pytest
+
+
+def _read(path):
+    with open(path, "rb") as f:
+        return f.read()
+
+
+@pytest.mark.parametrize("fixture", ["obs_v2_file", "obs_v3_file"])
+def test_obs_bytes_matches_file(fixture, request):
+    """Check that reading from bytes matches reading from the file"""
+    path = request.getfixturevalue(fixture)
+    from_file = read_rinex_obs(path, with_header=True)
+    result = read_rinex_obs_bytes(_read(path), with_header=True)
+
+    assert result.data.equals(from_file.data)
+    assert result.rx_position == pytest.approx(from_file.rx_position)
+    assert result.version == from_file.version
+    assert result.header == from_file.header
+
+
+def test_obs_file_like_and_buffers(obs_v3_file):
+    """Check file-like objects, bytearray and memoryview inputs"""
+    content = _read(obs_v3_file)
+    expected, _, _ = read_rinex_obs_bytes(content)
+
+    for data in (io.BytesIO(content), bytearray(content), memoryview(content)):
+        df, _, _ = read_rinex_obs_bytes(data)
+        assert df.shape == expected.shape
+
+
+def test_obs_bytes_gzip_and_hatanaka(
+    obs_v3_file, obs_v3_hatanaka_compressed_file, obs_v3_gzip_file
+):
+    """Check that gzip and Hatanaka-compressed content is decompressed"""
+    expected, _, _ = read_rinex_obs(obs_v3_file)
+
+    df_gz, _, _ = read_rinex_obs_bytes(gzip.compress(_read(obs_v3_file)))
+    df_crx, _, _ = read_rinex_obs_bytes(_read(obs_v3_hatanaka_compressed_file))
+    df_crx_gz, _, _ = read_rinex_obs_bytes(_read(obs_v3_gzip_file))
+
+    assert df_gz.shape == expected.shape
+    assert df_crx.shape == expected.shape
+    assert df_crx_gz.shape == expected.shape
+
+
+def test_obs_bytes_filters(obs_v3_file):
+    """Check that filters apply to in-memory content"""
+    df, _, _ = read_rinex_obs_bytes(_read(obs_v3_file), constellations=["E"])
+    assert df["sv"].str.starts_with("E").all()
+
+
+def test_nav_bytes(nav_v3_file):
+    """Check that navigation files can be read from memory"""
+    expected = read_rinex_nav(nav_v3_file)
+    nav = read_rinex_nav_bytes(io.BytesIO(_read(nav_v3_file)))
+    assert nav.keys() == expected.keys()
+    for constellation, df in nav.items():
+        assert df.equals(expected[constellation])
+
+
+def test_wrong_type_bytes(nav_v3_file, obs_v3_file):
+    """Check that a ValueError is raised for the wrong RINEX type"""
+    with pytest.raises(ValueError):
+        read_rinex_obs_bytes(_read(nav_v3_file))
+    with pytest.raises(ValueError):
+        read_rinex_nav_bytes(_read(obs_v3_file))
+
+
+def test_invalid_input():
+    """Check that a TypeError is raised for objects without content"""
+    with pytest.raises(TypeError):
+        read_rinex_obs_bytes(42)
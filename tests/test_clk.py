@@ -0,0 +1,71 @@
+from datetime import datetime, timezone
+
+from pytecggrs import CorruptedEpochError, WrongRinexTypeError, read_rinex_clk
+import pytest
+
+
+def _header_line(content, label):
+    return f"{content:<60}{label:<20}\n"
+
+
+def _clk(version, records):
+    return (
+        _header_line(f"{version:>9}           C                   M", "RINEX VERSION / TYPE")
+        + _header_line("CCLOCK              IGSACC @ GA & MIT   20241015 000000 UTC", "PGM / RUN BY / DATE")
+        + _header_line("   GPS", "TIME SYSTEM ID")
+        + _header_line("    18", "LEAP SECONDS")
+        + _header_line("IGS  IGS COMBINED", "ANALYSIS CENTER")
+        + _header_line("     1    IGS20", "# OF SOLN STA / TRF")
+        + _header_line("ASIR00ITA 12766M001   4641952000  1393033000  4133292000", "SOLN STA NAME / NUM")
+        + _header_line("G01 E11", "PRN LIST")
+        + _header_line("", "END OF HEADER")
+        + records
+    )
+
+
+def test_read_clk_v304(tmp_path):
+    """Check satellite and receiver clock records of a RINEX 3.04 file"""
+    records = (
+        "AS G01       2024 10 07 00 00  0.000000  2   -1.234567890123E-04  1.230000000000E-11\n"
+        "AS E11       2024 10 07 00 00 30.000000  4    5.000000000000E-04  2.000000000000E-11\n"
+        "    1.000000000000E-12  3.000000000000E-15\n"
+        "AR ASIR00ITA 2024 10 07 00 00  0.000000  1    1.000000000000E-08\n"
+        "CR ASIR00ITA 2024 10 07 00 00  0.000000  1    1.000000000000E-08\n"
+    )
+    path = tmp_path / "IGS0OPSFIN_20242810000_01D_30S_CLK.CLK"
+    path.write_text(_clk("3.04", records))
+
+    df, header = read_rinex_clk(str(path))
+
+    assert df.columns == [
+        "epoch", "time_system", "type", "name",
+        "bias", "bias_sigma", "rate", "rate_sigma", "acceleration", "acceleration_sigma",
+    ]
+    assert df["type"].to_list() == ["AS", "AS", "AR"]
+    assert df["name"].to_list() == ["G01", "E11", "ASIR00ITA"]
+    assert df["epoch"][1] == datetime(2024, 10, 7, 0, 0, 30, tzinfo=timezone.utc)
+
+    e11 = df.row(1, named=True)
+    assert e11["bias"] == pytest.approx(5e-4)
+    assert e11["rate"] == pytest.approx(1e-12)
+    assert e11["rate_sigma"] == pytest.approx(3e-15)
+    assert e11["acceleration"] is None
+    assert df.row(2, named=True)["bias_sigma"] is None
+
+    assert header["time_system"] == "GPST"
+    assert header["leap_seconds"] == 18
+    assert header["trf"] == "IGS20"
+    assert header["stations"] == ["ASIR00ITA"]
+    assert header["satellites"] == ["G01", "E11"]
+
+
+def test_read_clk_errors(tmp_path, nav_v3_file):
+    """Check errors for non-clock files and corrupted records"""
+    with pytest.raises(WrongRinexTypeError):
+        read_rinex_clk(nav_v3_file)
+
+    path = tmp_path / "broken.clk"
+    path.write_text(_clk("3.00", "AS G01  2024 1x 07 00 00  0.000000  1   -1.234567890123E-04\n"))
+    with pytest.raises(CorruptedEpochError) as excinfo:
+        read_rinex_clk(str(path))
+    assert excinfo.value.line == 10
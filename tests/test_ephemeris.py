@@ -25,6 +25,8 @@ def test_prepare_ephemeris_nav_v3(nav_v3_file):
     assert isinstance(ephemeris, dict)
     assert len(ephemeris) > 0
     for sat, eph in ephemeris.items():
+        assert sat == eph["sv"]
+        assert sat.startswith("G") and len(sat) == 3
         assert "gps_week" in eph
         assert "gps_seconds" in eph
         assert "sv" in eph
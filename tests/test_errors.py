@@ -0,0 +1,151 @@
+from pytecggrs import (
+    CorruptedEpochError,
+    DataFrameError,
+    RinexObsReader,
+    RinexParseError,
+    WrongRinexTypeError,
+    read_rinex_nav,
+    read_rinex_obs,
+    read_rinex_obs_bytes,
+    read_rinex_obs_many,
+)
+import polars as pl
+import pytest
+
+
+@pytest.fixture
+def corrupted_obs_file(obs_v3_file, tmp_path):
+    """Observation file whose second epoch line (line 76) is malformed"""
+    with open(obs_v3_file) as f:
+        lines = f.readlines()
+    assert lines[75].startswith("> 2024 10 07 00 00 30.0000000")
+    lines[75] = lines[75].replace("2024 10 07", "2024 1x 07")
+
+    path = tmp_path / "corrupted.rnx"
+    path.write_text("".join(lines))
+    return str(path)
+
+
+def test_hierarchy():
+    """Check that the exceptions extend the built-in ones raised so far"""
+    assert issubclass(RinexParseError, OSError)
+    assert issubclass(CorruptedEpochError, RinexParseError)
+    assert issubclass(WrongRinexTypeError, ValueError)
+    assert issubclass(DataFrameError, RuntimeError)
+
+
+def test_wrong_type(obs_v3_file, nav_v3_file):
+    """Check that WrongRinexTypeError carries the file path"""
+    with pytest.raises(WrongRinexTypeError) as exc_info:
+        read_rinex_obs(nav_v3_file)
+    assert exc_info.value.path == nav_v3_file
+    assert exc_info.value.line is None
+
+    with pytest.raises(WrongRinexTypeError):
+        read_rinex_nav(obs_v3_file)
+    with pytest.raises(WrongRinexTypeError):
+        RinexObsReader(nav_v3_file)
+
+
+def test_corrupted_epoch(obs_v3_file, corrupted_obs_file):
+    """Check that a malformed epoch is raised with its line, rather than being silently dropped"""
+    with pytest.raises(CorruptedEpochError) as exc_info:
+        read_rinex_obs(corrupted_obs_file)
+    err = exc_info.value
+    assert err.path == corrupted_obs_file
+    assert err.line == 76
+    assert err.epoch.startswith("2024 1x 07")
+
+    # The failure comes from that epoch alone
+    df_full, _, _ = read_rinex_obs(obs_v3_file)
+    result = read_rinex_obs(corrupted_obs_file, lenient=True)
+    assert result.data["epoch"].n_unique() == df_full["epoch"].n_unique() - 1
+    assert [s["start_line"] for s in result.skipped_epochs] == [76]
+
+
+def test_corrupted_epoch_bytes(corrupted_obs_file):
+    """Check that in-memory content reports the line but no path"""
+    with open(corrupted_obs_file, "rb") as f:
+        content = f.read()
+    with pytest.raises(CorruptedEpochError) as exc_info:
+        read_rinex_obs_bytes(content)
+    assert exc_info.value.path is None
+    assert exc_info.value.line == 76
+
+
+def test_corrupted_epoch_stream(corrupted_obs_file):
+    """Check that the corrupted epoch of a chunk is located by its line in the file"""
+    reader = RinexObsReader(corrupted_obs_file, chunk_size=2)
+    with pytest.raises(CorruptedEpochError) as exc_info:
+        next(reader)
+    assert exc_info.value.path == corrupted_obs_file
+    assert exc_info.value.line == 76
+
+
+def test_truncated_header(obs_v3_file, tmp_path):
+    """Check that a file without END OF HEADER raises RinexParseError"""
+    with open(obs_v3_file) as f:
+        head = "".join(f.readlines()[:20])
+    path = tmp_path / "truncated.rnx"
+    path.write_text(head)
+
+    with pytest.raises(RinexParseError) as exc_info:
+        RinexObsReader(str(path))
+    assert exc_info.value.path == str(path)
+
+
+def test_lenient_skips_corrupted_epoch(obs_v3_file, corrupted_obs_file):
+    """Check that lenient mode drops the corrupted epoch and reports it"""
+    full = read_rinex_obs(obs_v3_file, lenient=True)
+    result = read_rinex_obs(corrupted_obs_file, lenient=True, with_header=True)
+
+    assert full.skipped_epochs == []
+    assert full.header is None
+    assert "skipped_epochs" not in result.header
+    assert result.data["epoch"].n_unique() == full.data["epoch"].n_unique() - 1
+
+    [skipped] = result.skipped_epochs
+    assert skipped["start_line"] == 76
+    assert skipped["end_line"] == 96
+    assert skipped["epoch"].startswith("2024 1x 07")
+    assert skipped["reason"]
+
+
+def test_lenient_bytes_and_wide(corrupted_obs_file):
+    """Check lenient mode of the in-memory and wide readers"""
+    from pytecggrs import read_rinex_obs_wide
+
+    with open(corrupted_obs_file, "rb") as f:
+        content = f.read()
+    assert len(read_rinex_obs_bytes(content, lenient=True).skipped_epochs) == 1
+
+    result = read_rinex_obs_wide(corrupted_obs_file, lenient=True)
+    assert len(result.skipped_epochs) == 1
+    assert result.data.height > 0
+
+
+def test_lenient_stream(obs_v3_file, corrupted_obs_file):
+    """Check that the streaming reader drops corrupted epochs with line numbers of the file"""
+    reader = RinexObsReader(corrupted_obs_file, chunk_size=2, lenient=True)
+    df_stream = pl.concat(list(reader))
+    df_full, _, _ = read_rinex_obs(obs_v3_file)
+
+    assert df_stream["epoch"].n_unique() == df_full["epoch"].n_unique() - 1
+    [skipped] = reader.skipped_epochs
+    assert skipped["start_line"] == 76
+    assert skipped["end_line"] == 96
+
+
+def test_lenient_many(obs_v3_file, corrupted_obs_file):
+    """Check that batches report the epochs skipped per file in lenient mode"""
+    _, errors = read_rinex_obs_many([corrupted_obs_file])
+    assert set(errors) == {corrupted_obs_file}
+    assert isinstance(errors[corrupted_obs_file], CorruptedEpochError)
+    assert errors[corrupted_obs_file].path == corrupted_obs_file
+    assert errors[corrupted_obs_file].line == 76
+
+    df, errors, skipped = read_rinex_obs_many([obs_v3_file, corrupted_obs_file], lenient=True)
+    assert errors == {}
+    assert skipped[obs_v3_file] == []
+    assert [s["start_line"] for s in skipped[corrupted_obs_file]] == [76]
+    assert df.height > 0
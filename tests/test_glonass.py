@@ -0,0 +1,41 @@
+from pytecggrs import read_glonass_channels
+import pytest
+
+
+def test_glonass_channels_from_obs(obs_v3_file):
+    """Check the slot/channel map read from the observation header"""
+    channels, conflicts = read_glonass_channels(obs_path=obs_v3_file)
+    assert len(channels) == 24
+    assert channels["R01"] == 1
+    assert channels["R02"] == -4
+    assert conflicts == []
+
+
+def test_glonass_channels_from_nav(nav_v3_file):
+    """Check the slot/channel map read from GLONASS ephemerides"""
+    channels, conflicts = read_glonass_channels(nav_path=nav_v3_file)
+    assert len(channels) > 0
+    assert all(sv.startswith("R") for sv in channels)
+    assert all(-7 <= k <= 6 for k in channels.values())
+    assert conflicts == []
+
+
+def test_glonass_channels_merged(obs_v3_file, nav_v3_file):
+    """Check that both sources are merged and conflicts are reported"""
+    obs_channels, _ = read_glonass_channels(obs_path=obs_v3_file)
+    nav_channels, _ = read_glonass_channels(nav_path=nav_v3_file)
+    channels, conflicts = read_glonass_channels(
+        obs_path=obs_v3_file, nav_path=nav_v3_file
+    )
+
+    assert set(channels) == set(obs_channels) | set(nav_channels)
+    for conflict in conflicts:
+        assert obs_channels[conflict["sv"]] == conflict["obs"]
+        assert nav_channels[conflict["sv"]] == conflict["nav"]
+        assert channels[conflict["sv"]] == conflict["nav"]
+
+
+def test_glonass_channels_no_source():
+    """Check that a ValueError is raised when no file is given"""
+    with pytest.raises(ValueError):
+        read_glonass_channels()
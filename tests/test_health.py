@@ -0,0 +1,60 @@
+import polars as pl
+from pytecggrs import read_rinex_nav, screen_nav
+
+
+def test_screen_semantics():
+    """Check the health semantics of each constellation"""
+    nav = pl.DataFrame(
+        {
+            "sv": ["G01", "G02", "E03", "E04", "C05", "R06", "E07"],
+            "msg_type": ["LNAV", "LNAV", "INAV", "FNAV", "D1", "FDMA", "INAV"],
+            "health": [0.0, 63.0, 0.0, 0.0, None, 1.0, 0.0],
+            "satH1": [None, None, None, None, 1.0, None, None],
+            "sisa": [None, None, 3.12, 3.12, None, None, -1.0],
+            "accuracy": [2.0, 2.0, None, None, 2.0, None, None],
+        }
+    )
+    # E04: F/NAV only carries the E5a status, here E1B out of service
+    nav = nav.with_columns(
+        pl.when(pl.col("sv").is_in(["E03", "E04"]))
+        .then(pl.lit(2.0))
+        .otherwise(pl.col("health"))
+        .alias("health")
+    )
+
+    screened = screen_nav(nav)
+    assert screened["healthy"].to_list() == [True, False, False, True, False, False, False]
+    issues = dict(zip(screened["sv"], screened["health_issue"]))
+    assert issues["G02"] == "SV health 0x3F (NAV data)"
+    assert issues["E03"] == "E1B HS out of service"
+    assert issues["C05"] == "SatH1 not good"
+    assert issues["R06"] == "Bn unhealthy"
+    assert issues["E07"] == "SISA no accuracy prediction available"
+
+
+def test_screen_accuracy_and_drop():
+    """Check the accuracy threshold and the removal of unusable records"""
+    nav = pl.DataFrame(
+        {
+            "sv": ["G01", "G02", "G03"],
+            "health": [0.0, 0.0, 0.0],
+            "accuracy": [2.0, 24.0, 6144.0],
+        }
+    )
+
+    assert screen_nav(nav)["healthy"].to_list() == [True, True, False]
+    assert screen_nav(nav, max_accuracy=10.0)["healthy"].to_list() == [True, False, False]
+
+    dropped = screen_nav(nav, drop=True, max_accuracy=10.0)
+    assert dropped.columns == nav.columns
+    assert dropped["sv"].to_list() == ["G01"]
+
+
+def test_screen_nav_file(nav_v3_file):
+    """Check that screening keeps the frames of a navigation file intact"""
+    for df in read_rinex_nav(nav_v3_file).values():
+        screened = screen_nav(df)
+        assert screened.height == df.height
+        assert screened.columns == df.columns + ["healthy", "health_issue"]
+        assert (screened["healthy"] == screened["health_issue"].is_null()).all()
+        assert screen_nav(df, drop=True).height == screened["healthy"].sum()
@@ -0,0 +1,106 @@
+from datetime import datetime, timedelta, timezone
+import math
+import warnings
+
+import polars as pl
+from pytecggrs import interpolate_sp3
+import pytest
+
+START = datetime(2024, 10, 7, tzinfo=timezone.utc)
+RADIUS = 26_560_000.0
+OMEGA = 2 * math.pi / 43_082.0
+
+
+def _orbit(t):
+    """Position and velocity of a circular, inclined test orbit at t seconds"""
+    c, s = math.cos(OMEGA * t), math.sin(OMEGA * t)
+    return (
+        (RADIUS * c, RADIUS * s, 0.5 * RADIUS * s),
+        (-RADIUS * OMEGA * s, RADIUS * OMEGA * c, 0.5 * RADIUS * OMEGA * c),
+    )
+
+
+def _sp3(seconds):
+    positions = [_orbit(t)[0] for t in seconds]
+    return pl.DataFrame(
+        {
+            "epoch": [START + timedelta(seconds=t) for t in seconds],
+            "time_system": "GPST",
+            "sv": "G01",
+            "x": [p[0] for p in positions],
+            "y": [p[1] for p in positions],
+            "z": [p[2] for p in positions],
+            "clock": [1e-4 + 1e-9 * t for t in seconds],
+        }
+    )
+
+
+def _epochs(seconds):
+    return pl.DataFrame(
+        {"epoch": [START + timedelta(seconds=t) for t in seconds], "sv": "G01"}
+    )
+
+
+SP3 = _sp3(range(0, 86_401, 900))
+
+
+def _check(df, seconds, tol=1e-3):
+    for row, t in zip(df.iter_rows(named=True), seconds):
+        position, velocity = _orbit(t)
+        assert [row["x"], row["y"], row["z"]] == pytest.approx(position, abs=tol)
+        assert [row["vx"], row["vy"], row["vz"]] == pytest.approx(velocity, abs=1e-5)
+
+
+def test_interpolate_lagrange():
+    """Check positions, velocities and clocks in the middle of an arc"""
+    seconds = [36_000 + 30 * k for k in range(60)]
+    with warnings.catch_warnings():
+        warnings.simplefilter("error")
+        df = interpolate_sp3(SP3, _epochs(seconds))
+
+    assert df.columns == ["epoch", "sv", "x", "y", "z", "vx", "vy", "vz", "clock", "edge"]
+    assert df.height == len(seconds)
+    assert not df["edge"].any()
+    _check(df, seconds)
+    assert df["clock"].to_list() == pytest.approx([1e-4 + 1e-9 * t for t in seconds])
+
+
+def test_interpolate_chebyshev():
+    """Check the least squares Chebyshev fit"""
+    seconds = [40_000 + 30 * k for k in range(20)]
+    df = interpolate_sp3(SP3, _epochs(seconds), method="chebyshev", order=10, nodes=13)
+    _check(df, seconds)
+
+
+def test_interpolate_edges_and_gaps():
+    """Check edge-of-arc flags, gaps and epochs outside the orbits"""
+    sp3 = SP3.filter(
+        (pl.col("epoch") < START + timedelta(hours=10))
+        | (pl.col("epoch") > START + timedelta(hours=12))
+    )
+    seconds = [60, 40_000, 86_400, 90_000]
+
+    with pytest.warns(RuntimeWarning, match="edge"):
+        df = interpolate_sp3(sp3, _epochs(seconds))
+
+    assert df["edge"].to_list() == [True, None, True, None]
+    assert df["x"][1] is None and df["x"][3] is None
+    _check(df[[0, 2]], [60, 86_400], tol=0.1)
+
+
+def test_interpolate_day_boundary():
+    """Check that consecutive daily files sharing an epoch can be concatenated"""
+    seconds = [43_200 + 30 * k for k in range(20)]
+    merged = pl.concat([SP3[:49], SP3[48:]])
+
+    assert interpolate_sp3(merged, _epochs(seconds)).equals(
+        interpolate_sp3(SP3, _epochs(seconds))
+    )
+
+
+def test_interpolate_invalid_nodes():
+    """Check that Lagrange interpolation requires order + 1 nodes"""
+    with pytest.raises(ValueError):
+        interpolate_sp3(SP3, _epochs([0]), nodes=13)
+    with pytest.raises(ValueError):
+        interpolate_sp3(SP3, _epochs([0]), method="hermite")
@@ -0,0 +1,162 @@
+from datetime import datetime, timedelta, timezone
+
+import polars as pl
+from pytecggrs import WrongRinexTypeError, read_ionex, write_ionex
+import pytest
+
+
+def _line(content, label):
+    return f"{content:<60}{label:<20}\n"
+
+
+def _map(kind, index, epoch, bands, exponent=None):
+    text = _line(f"{index:6d}", f"START OF {kind} MAP")
+    text += _line("".join(f"{v:6d}" for v in epoch), "EPOCH OF CURRENT MAP")
+    if exponent is not None:
+        text += _line(f"{exponent:6d}", "EXPONENT")
+    for lat, values in bands:
+        text += _line(f"  {lat:6.1f}{-10.0:6.1f}{10.0:6.1f}{10.0:6.1f}{450.0:6.1f}", "LAT/LON1/LON2/DLON/H")
+        text += "".join(f"{v:5d}" for v in values) + "\n"
+    return text + _line(f"{index:6d}", f"END OF {kind} MAP")
+
+
+IONEX = (
+    _line("     1.0            IONOSPHERE MAPS     GPS", "IONEX VERSION / TYPE")
+    + _line("TECGG               UNIPA               15-oct-24 00:00", "PGM / RUN BY / DATE")
+    + _line("Regional test map", "DESCRIPTION")
+    + _line("  2024    10     7     0     0     0", "EPOCH OF FIRST MAP")
+    + _line("  2024    10     7     0     0     0", "EPOCH OF LAST MAP")
+    + _line("     0", "INTERVAL")
+    + _line("     1", "# OF MAPS IN FILE")
+    + _line("  COSZ", "MAPPING FUNCTION")
+    + _line("    10.0", "ELEVATION CUTOFF")
+    + _line("  6371.0", "BASE RADIUS")
+    + _line("     2", "MAP DIMENSION")
+    + _line("   450.0 450.0   0.0", "HGT1 / HGT2 / DHGT")
+    + _line("    40.0  35.0  -5.0", "LAT1 / LAT2 / DLAT")
+    + _line("   -10.0  10.0  10.0", "LON1 / LON2 / DLON")
+    + _line("    -1", "EXPONENT")
+    + _line("DIFFERENTIAL CODE BIASES", "START OF AUX DATA")
+    + _line("   G01    -7.123     0.012", "PRN / BIAS / RMS")
+    + _line("   G  ALBH 40129M003          -2.080     0.015", "STATION / BIAS / RMS")
+    + _line("DIFFERENTIAL CODE BIASES", "END OF AUX DATA")
+    + _line("", "END OF HEADER")
+    + _map("TEC", 1, (2024, 10, 7, 0, 0, 0), [(40.0, [105, 110, 9999]), (35.0, [120, 125, 130])])
+    + _map("RMS", 1, (2024, 10, 7, 0, 0, 0), [(40.0, [12, 13, 14]), (35.0, [15, 16, 17])], exponent=-2)
+    + _line("", "END OF FILE")
+)
+
+
+def test_read_ionex(tmp_path):
+    """Check TEC and RMS grids, DCBs and header of an IONEX file"""
+    path = tmp_path / "regional.24i"
+    path.write_text(IONEX)
+
+    grid, dcbs, header = read_ionex(str(path))
+
+    assert grid.columns == ["epoch", "lat", "lon", "height", "tec", "rms"]
+    assert grid.height == 6
+    assert grid["epoch"].unique().to_list() == [datetime(2024, 10, 7, tzinfo=timezone.utc)]
+    assert grid["lat"].to_list() == [40.0, 40.0, 40.0, 35.0, 35.0, 35.0]
+    assert grid["lon"].to_list() == [-10.0, 0.0, 10.0, -10.0, 0.0, 10.0]
+    # 9999 marks missing values
+    assert grid["tec"][2] is None
+    assert grid["tec"].drop_nulls().to_list() == pytest.approx([10.5, 11.0, 12.0, 12.5, 13.0])
+    assert grid["rms"].to_list() == pytest.approx([0.12, 0.13, 0.14, 0.15, 0.16, 0.17])
+
+    assert dcbs["kind"].to_list() == ["satellite", "station"]
+    assert dcbs["name"].to_list() == ["G01", "ALBH"]
+    assert dcbs["domes"].to_list() == [None, "40129M003"]
+    assert dcbs["bias"].to_list() == pytest.approx([-7.123, -2.080])
+    assert dcbs["rms"].to_list() == pytest.approx([0.012, 0.015])
+
+    assert header["version"] == "1.0"
+    assert header["first_epoch"] == "2024-10-07T00:00:00"
+    assert header["mapping_function"] == "COSZ"
+    assert header["latitudes"] == [40.0, 35.0, -5.0]
+    assert header["exponent"] == -1
+
+
+def test_read_ionex_wrong_type(nav_v3_file):
+    """Check that other files are rejected"""
+    with pytest.raises(WrongRinexTypeError):
+        read_ionex(nav_v3_file)
+
+
+def test_write_ionex_roundtrip(tmp_path):
+    """Check that a written IONEX file reads back the same grid, DCBs and header"""
+    path = tmp_path / "regional.24i"
+    path.write_text(IONEX)
+    grid, dcbs, header = read_ionex(str(path))
+
+    out = tmp_path / "written.24i.gz"
+    write_ionex(str(out), grid, header=header, dcbs=dcbs, version="1.0")
+    grid2, dcbs2, header2 = read_ionex(str(out))
+
+    assert grid2.select("epoch", "lat", "lon", "height").equals(grid.select("epoch", "lat", "lon", "height"))
+    assert grid2["tec"][2] is None
+    assert grid2["tec"].drop_nulls().to_list() == pytest.approx(grid["tec"].drop_nulls().to_list())
+    assert grid2["rms"].to_list() == pytest.approx(grid["rms"].to_list())
+    assert dcbs2.equals(dcbs)
+
+    for key in ["version", "program", "run_by", "description", "first_epoch", "num_maps",
+                "mapping_function", "elevation_cutoff", "base_radius", "map_dimension",
+                "heights", "latitudes", "longitudes"]:
+        assert header2[key] == header[key]
+
+
+def test_write_ionex_exponents(tmp_path):
+    """Check per-map exponents, interval and invalid grids"""
+    start = datetime(2024, 10, 7, tzinfo=timezone.utc)
+    grid = pl.DataFrame(
+        {
+            "epoch": [start] * 4 + [start + timedelta(hours=1)] * 4,
+            "lat": [10.0, 10.0, 5.0, 5.0] * 2,
+            "lon": [0.0, 5.0, 0.0, 5.0] * 2,
+            "tec": [12.34, 15.0, 19.99, 0.5, 250.1, 100.0, 50.0, 1.0],
+        }
+    )
+    out = tmp_path / "maps.24i"
+    write_ionex(str(out), grid)
+    text = out.read_text()
+
+    _, _, header = read_ionex(str(out))
+    assert header["version"] == "1.1"
+    assert header["interval"] == 3600
+    assert header["num_maps"] == 2
+    assert header["exponent"] == -1
+    # The first map keeps one more digit
+    assert _line("    -2", "EXPONENT") in text
+    assert read_ionex(str(out))[0]["tec"].to_list() == pytest.approx(grid["tec"].to_list())
+    assert "START OF RMS MAP" not in text
+
+    with pytest.raises(ValueError):
+        write_ionex(str(out), grid, exponent=-3)
+    with pytest.raises(ValueError):
+        write_ionex(str(out), grid.with_columns(lon=pl.Series([0.0, 5.0, 0.0, 15.0] * 2)))
+
+
+def test_write_ionex_missing_and_duplicates(tmp_path):
+    """Check that NaN values are written as missing and that colliding rows are rejected"""
+    start = datetime(2024, 10, 7, tzinfo=timezone.utc)
+    grid = pl.DataFrame(
+        {
+            "epoch": [start] * 4,
+            "lat": [10.0, 10.0, 5.0, 5.0],
+            "lon": [0.0, 5.0, 0.0, 5.0],
+            "tec": [12.3, float("nan"), 19.9, None],
+            "rms": [float("nan"), 1.5, 1.0, 2.0],
+        }
+    )
+    out = tmp_path / "missing.24i"
+    write_ionex(str(out), grid)
+
+    written, _, _ = read_ionex(str(out))
+    assert written["tec"].to_list()[1:] == [None, pytest.approx(19.9), None]
+    assert written["rms"][0] is None
+
+    # Epochs are truncated to whole seconds: these two rows would share a cell
+    late = grid.with_columns(epoch=pl.Series([start, start, start, start + timedelta(milliseconds=500)]))
+    late = late.with_columns(lat=pl.Series([10.0, 10.0, 5.0, 5.0]), lon=pl.Series([0.0, 5.0, 0.0, 0.0]))
+    with pytest.raises(ValueError):
+        write_ionex(str(out), late)
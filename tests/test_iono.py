@@ -0,0 +1,86 @@
+from datetime import datetime, timezone
+
+from pytecggrs import WrongRinexTypeError, read_rinex_nav_iono
+import pytest
+
+
+def _header_line(content, label):
+    return f"{content:<60}{label:<20}\n"
+
+
+@pytest.fixture
+def nav_v4_file(tmp_path):
+    """Minimal RINEX 4 navigation file with one ION message per model"""
+
+    def message(header, values):
+        lines = [header + "\n"]
+        first, rest = values[:3], values[3:]
+        lines.append("    2022 06 10 01 30 00" + "".join(f"{v:19.12E}" for v in first) + "\n")
+        for i in range(0, len(rest), 4):
+            lines.append("    " + "".join(f"{v:19.12E}" for v in rest[i : i + 4]) + "\n")
+        return "".join(lines)
+
+    content = (
+        _header_line("     4.00           N: GNSS NAV DATA    M: MIXED", "RINEX VERSION / TYPE")
+        + _header_line("", "END OF HEADER")
+        + message("> ION G01 LNAV", [1e-8, 2e-8, -6e-8, -1e-7, 9e4, 1.4e5, -6e4, -5e5, 1.0])
+        + message("> ION E08 IFNV", [44.75, 0.171875, 0.00238, 0.0])
+        + message("> ION C21 CNVX", [float(i) for i in range(1, 10)])
+    )
+    path = tmp_path / "nav_v4.rnx"
+    path.write_text(content)
+    return str(path)
+
+
+def test_iono_header_v3(nav_v3_file):
+    """Check the coefficients found in RINEX 3 header records"""
+    iono = read_rinex_nav_iono(nav_v3_file)
+    assert set(iono) == {"klobuchar", "nequick_g", "bdgim"}
+
+    klobuchar = iono["klobuchar"]
+    gps = klobuchar.filter(constellation="GPS")
+    assert gps.height == 1
+    assert gps["alpha0"][0] == pytest.approx(2.9802e-08)
+    assert gps["beta3"][0] == pytest.approx(2.6214e05)
+    assert (klobuchar["source"] == "header").all()
+
+    # BDS coefficients are given per time mark and broadcasting SV
+    beidou = klobuchar.filter(constellation="BeiDou")
+    assert beidou.height == 18
+    assert beidou["alpha0"].null_count() == 0
+    assert beidou["beta0"].null_count() == 0
+    assert beidou.filter(time_mark="A")["sv"].to_list() == ["C09"]
+
+    nequick = iono["nequick_g"]
+    assert nequick.height == 1
+    assert nequick["ai0"][0] == 0.0
+    assert iono["bdgim"].is_empty()
+
+
+def test_iono_messages_v4(nav_v4_file):
+    """Check RINEX 4 ION messages and their transmission times"""
+    iono = read_rinex_nav_iono(nav_v4_file)
+    expected_epoch = datetime(2022, 6, 10, 1, 30, tzinfo=timezone.utc)
+
+    gps = iono["klobuchar"].row(0, named=True)
+    assert gps["source"] == "message"
+    assert gps["sv"] == "G01"
+    assert gps["msg_type"] == "LNAV"
+    assert gps["time_system"] == "GPST"
+    assert gps["epoch"] == expected_epoch
+    assert gps["alpha0"] == pytest.approx(1e-8)
+    assert gps["beta3"] == pytest.approx(-5e5)
+    assert gps["region_code"] == 1.0
+
+    galileo = iono["nequick_g"].row(0, named=True)
+    assert galileo["ai0"] == pytest.approx(44.75)
+    assert galileo["idf"] == 0.0
+
+    bdgim = iono["bdgim"].row(0, named=True)
+    assert [bdgim[f"alpha{i}"] for i in range(1, 10)] == pytest.approx(range(1, 10))
+
+
+def test_iono_wrong_type(obs_v3_file):
+    """Check that observation files are rejected"""
+    with pytest.raises(WrongRinexTypeError):
+        read_rinex_nav_iono(obs_v3_file)
@@ -0,0 +1,65 @@
+from datetime import datetime, timezone
+
+from pytecggrs import read_rinex_nav, read_rinex_obs
+from polars import Datetime
+import polars as pl
+import pytest
+
+
+def test_nav_columns(nav_v3_file):
+    """Check the toc, sv and message type columns of navigation frames"""
+    nav = read_rinex_nav(nav_v3_file)
+
+    for constellation, df in nav.items():
+        assert df.columns[:5] == ["row_id", "toc", "time_system", "sv", "msg_type"]
+        assert df.schema["toc"] == Datetime("ns", "UTC")
+        assert df["sv"].str.contains(r"^[GRECJIS]\d{2}$").all()
+        assert df["msg_type"].null_count() == 0
+
+    assert nav["GPS"]["sv"].str.starts_with("G").all()
+    assert nav["GPS"]["time_system"].unique().to_list() == ["GPST"]
+    assert nav["GLONASS"]["time_system"].unique().to_list() == ["UTC"]
+
+
+def test_nav_joins_obs(nav_v3_file, obs_v3_file):
+    """Check that SV identifiers of both readers join without conversion"""
+    nav = read_rinex_nav(nav_v3_file)["GPS"]
+    obs, _, _ = read_rinex_obs(obs_v3_file, constellations=["G"])
+
+    joined = obs.select("sv").unique().join(nav.select("sv").unique(), on="sv")
+    assert joined.height > 0
+
+
+def test_nav_msg_types(nav_v3_file):
+    """Check the message types inferred for RINEX 3 records"""
+    nav = read_rinex_nav(nav_v3_file)
+
+    assert set(nav["GPS"]["msg_type"].unique()) == {"LNAV"}
+    assert set(nav["GLONASS"]["msg_type"].unique()) == {"FDMA"}
+    assert set(nav["Galileo"]["msg_type"].unique()) == {"INAV", "FNAV"}
+
+    # E01 record of 2023-04-29 05:30 GST, from data source 258 (F/NAV E5a)
+    fnav = nav["Galileo"].filter(sv="E01", toc=datetime(2023, 4, 29, 5, 30, tzinfo=timezone.utc))
+    assert fnav["dataSrc"].to_list() == [258.0]
+    assert fnav["msg_type"].to_list() == ["FNAV"]
+    assert (nav["Galileo"].filter(pl.col("dataSrc") == 258)["msg_type"] == "FNAV").all()
+    assert (nav["Galileo"].filter(pl.col("dataSrc") == 517)["msg_type"] == "INAV").all()
+
+    beidou = nav["BeiDou"].with_columns(prn=pl.col("sv").str.slice(1).cast(pl.Int32))
+    geo = (beidou["prn"] <= 5) | (beidou["prn"] >= 59)
+    assert (beidou.filter(geo)["msg_type"] == "D2").all()
+    assert (beidou.filter(~geo)["msg_type"] == "D1").all()
+
+
+def test_nav_msg_type_filter(nav_v3_file):
+    """Check the message type filter"""
+    nav = read_rinex_nav(nav_v3_file, msg_types=["inav"])
+
+    assert list(nav) == ["Galileo"]
+    assert set(nav["Galileo"]["msg_type"].unique()) == {"INAV"}
+
+
+def test_nav_invalid_msg_type(nav_v3_file):
+    """Check that a ValueError is raised for unknown message types"""
+    with pytest.raises(ValueError):
+        read_rinex_nav(nav_v3_file, msg_types=["XNAV"])
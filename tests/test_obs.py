@@ -1,5 +1,8 @@
-from pytecggrs import read_rinex_obs
-from polars import DataFrame
+from datetime import datetime, timedelta, timezone
+
+from pytecggrs import read_rinex_obs, read_rinex_obs_wide
+from polars import DataFrame, Datetime
+import polars as pl
 import pytest
 
 
@@ -40,6 +43,190 @@ def test_read_rinex_obs_valid_v3_hatanaka(obs_v3_hatanaka_compressed_file):
     assert version.startswith("3")
 
 
+def test_read_rinex_obs_lli_ssi_columns(obs_v3_file):
+    """Check that LLI and SSI flags are exposed and decoded per bit"""
+    df, _, _ = read_rinex_obs(obs_v3_file)
+    for col in ["lli", "ssi", "loss_of_lock", "half_cycle", "anti_spoofing"]:
+        assert col in df.columns
+
+    def flags(sv, observable):
+        row = df.filter(
+            (pl.col("epoch") == datetime(2024, 10, 7, tzinfo=timezone.utc))
+            & (pl.col("sv") == sv)
+            & (pl.col("observable") == observable)
+        )
+        assert row.height == 1
+        return row.select("lli", "ssi", "loss_of_lock", "half_cycle", "anti_spoofing").row(0)
+
+    # "125956173.11646" and "113279473.93815" in the first epoch of the file
+    assert flags("E13", "L1C") == (4, 6, False, False, True)
+    assert flags("E15", "L7Q") == (1, 5, True, False, False)
+
+
+def test_read_rinex_obs_epoch_datetime(obs_v3_file):
+    """Check that epochs are returned as a Datetime column with their time system"""
+    df, _, _ = read_rinex_obs(obs_v3_file)
+    assert df.schema["epoch"] == Datetime("ns", "UTC")
+    assert df["time_system"].unique().to_list() == ["GPST"]
+
+
+def test_read_rinex_obs_timescale_conversion(obs_v3_file):
+    """Check that epochs are converted to the requested time scale"""
+    df_gpst, _, _ = read_rinex_obs(obs_v3_file, timescale="GPST")
+    df_utc, _, _ = read_rinex_obs(obs_v3_file, timescale="UTC")
+    df_tai, _, _ = read_rinex_obs(obs_v3_file, timescale="TAI")
+
+    assert df_utc["time_system"].unique().to_list() == ["UTC"]
+    # 18 leap seconds between GPST and UTC, 19 s between TAI and GPST (2024)
+    assert df_gpst["epoch"][0] - df_utc["epoch"][0] == timedelta(seconds=18)
+    assert df_tai["epoch"][0] - df_gpst["epoch"][0] == timedelta(seconds=19)
+
+
+def test_read_rinex_obs_invalid_timescale(obs_v3_file):
+    """Check that a ValueError is raised with an unsupported time scale"""
+    with pytest.raises(ValueError):
+        read_rinex_obs(obs_v3_file, timescale="XYZ")
+
+
+def test_read_rinex_obs_wide_matches_pivot(obs_v3_file):
+    """Check that the wide output matches a pivot of the long output"""
+    df_long, coords_long, version_long = read_rinex_obs(obs_v3_file)
+    df_wide, coords_wide, version_wide = read_rinex_obs_wide(obs_v3_file)
+
+    assert coords_long == coords_wide
+    assert version_long == version_wide
+    assert df_wide.columns[:5] == ["epoch", "time_system", "sv", "epoch_flag", "clock_offset"]
+    assert df_wide.select(["epoch", "sv"]).is_duplicated().sum() == 0
+
+    df_pivot = df_long.pivot(
+        values="value",
+        index=["epoch", "sv"],
+        on="observable",
+        aggregate_function="first",
+    )
+    assert df_wide.height == df_pivot.height
+    assert set(df_wide.columns[5:]) == set(df_pivot.columns[2:])
+
+    observable = df_wide.columns[5]
+    joined = df_wide.join(df_pivot, on=["epoch", "sv"], suffix="_pivot")
+    assert joined[observable].equals(joined[f"{observable}_pivot"])
+
+
+def test_read_rinex_obs_filter_constellation_and_observables(obs_v3_file):
+    """Check constellation and observable filtering while reading"""
+    df, _, _ = read_rinex_obs(
+        obs_v3_file, constellations=["G"], observables=["C1C", "L1C"]
+    )
+    assert df.shape[0] > 0
+    assert df["sv"].str.starts_with("G").all()
+    assert set(df["observable"].unique().to_list()) <= {"C1C", "L1C"}
+
+
+def test_read_rinex_obs_filter_sv_and_regex(obs_v3_file):
+    """Check SV and observable regex filtering while reading"""
+    df, _, _ = read_rinex_obs(obs_v3_file, svs=["E11", "G05"], observable_regex="^L")
+    assert set(df["sv"].unique().to_list()) <= {"E11", "G05"}
+    assert df["observable"].str.starts_with("L").all()
+
+
+def test_read_rinex_obs_filter_time_window(obs_v3_file):
+    """Check time window and decimation filtering while reading"""
+    df, _, _ = read_rinex_obs(
+        obs_v3_file,
+        start="2024-10-07T01:00:00 GPST",
+        end="2024-10-07T02:00:00 GPST",
+        interval=300,
+    )
+    epochs = df["epoch"].unique().sort()
+    assert epochs.len() == 13
+    assert epochs[0].hour == 1 and epochs[-1].hour == 2
+    assert all(e.minute % 5 == 0 and e.second == 0 for e in epochs)
+
+
+def test_read_rinex_obs_wide_filter(obs_v3_file):
+    """Check that filters also apply to the wide output"""
+    df, _, _ = read_rinex_obs_wide(
+        obs_v3_file, constellations=["GPS"], observables=["C1C", "L1C"]
+    )
+    assert df["sv"].str.starts_with("G").all()
+    assert set(df.columns[5:]) <= {"C1C", "L1C"}
+
+
+def test_read_rinex_obs_invalid_filter(obs_v3_file):
+    """Check that a ValueError is raised with invalid filter arguments"""
+    with pytest.raises(ValueError):
+        read_rinex_obs(obs_v3_file, constellations=["XYZ"])
+    with pytest.raises(ValueError):
+        read_rinex_obs(obs_v3_file, interval=-30)
+
+
+def test_read_rinex_obs_header_v3(obs_v3_file):
+    """Check the header metadata returned alongside a RINEX v3 observation file"""
+    result = read_rinex_obs(obs_v3_file, with_header=True)
+    header = result.header
+    assert header["version"] == result.version
+    assert header["system"] == "M"
+    assert header["marker_name"] == "ASIR00ITA"
+    assert header["marker_number"] == "14668M001"
+    assert header["receiver_type"] == "LEICA GR30"
+    assert header["receiver_firmware"] == "4.80/7.900"
+    assert header["antenna_type"] == "LEIAR20"
+    assert header["antenna_delta_hen"] == (0.0, 0.0, 0.0)
+    assert header["rx_position"] == pytest.approx(result.rx_position)
+    assert header["interval"] == 30.0
+    assert header["time_of_first_obs"].startswith("2024-10-07T00:00:00")
+    assert header["leap_seconds"] == 18
+    assert len(header["obs_types"]["G"]) == 16
+    assert header["obs_types"]["C"] == ["C2I", "L2I", "D2I", "S2I", "C7I", "L7I", "D7I", "S7I"]
+    assert len(header["glonass_slots"]) == 24
+    assert header["glonass_slots"]["R02"] == -4
+    assert header["glonass_cod_phs_bis"]["C1C"] == pytest.approx(-71.94)
+    l2s = next(
+        s for s in header["phase_shifts"] if s["system"] == "G" and s["observable"] == "L2S"
+    )
+    assert l2s["correction"] == pytest.approx(-0.25)
+
+
+def test_read_rinex_obs_header_v2(obs_v2_file):
+    """Check the header metadata returned alongside a RINEX v2 observation file"""
+    result = read_rinex_obs(obs_v2_file, with_header=True)
+    assert result.epochs is None and result.skipped_epochs is None
+
+    header = result.header
+    assert header["marker_name"] == "CGTC"
+    assert header["receiver_type"] == "TRIMBLE NETRS"
+    assert header["obs_types"]["G"] == ["L1", "L2", "C1", "P1", "P2", "S1", "S2"]
+    assert header["interval"] == 15.0
+
+
+def test_read_rinex_obs_epochs_with_events(obs_v3_file, tmp_path):
+    """Check that event epochs, which carry no signal, are listed in the per-epoch table"""
+    with open(obs_v3_file) as f:
+        lines = f.readlines()
+    event = [
+        "> 2024 10 07 00 00 15.0000000  4  2\n",
+        f"{'ANTENNA RAISED':<60}COMMENT\n",
+        f"{'ASIR00ITA':<60}MARKER NAME\n",
+    ]
+    path = tmp_path / "events.rnx"
+    path.write_text("".join(lines[:75] + event + lines[75:]))
+
+    result = read_rinex_obs(str(path), with_epochs=True)
+    df, epochs = result.data, result.epochs
+    df_full, _, _ = read_rinex_obs(obs_v3_file)
+
+    assert epochs.columns == ["epoch", "time_system", "epoch_flag", "num_sv", "event_lines", "clock_offset"]
+    assert epochs.height == df_full["epoch"].n_unique() + 1
+    assert epochs["num_sv"][0] == 20
+
+    event_row = epochs.filter(pl.col("epoch_flag") == 4).row(0, named=True)
+    assert event_row["epoch"] == datetime(2024, 10, 7, 0, 0, 15, tzinfo=timezone.utc)
+    assert event_row["event_lines"] == 2
+    assert event_row["num_sv"] is None
+    assert df.filter(pl.col("epoch") == event_row["epoch"]).is_empty()
+    assert df["epoch"].n_unique() == df_full["epoch"].n_unique()
+
+
 # TODO
 # def test_read_rinex_obs_valid_v3_gzipped(obs_v3_gzip_file):
 #     df, (x, y, z) = read_rinex_obs(obs_v3_gzip_file)
@@ -52,3 +239,24 @@ def test_read_rinex_obs_nonexistent_file(invalid_file):
     """Check that a FileNotFoundError is raised with a non-existent file"""
     with pytest.raises(FileNotFoundError):
         read_rinex_obs(invalid_file)
+
+
+def test_read_rinex_obs_epoch_flag_and_clock_offset(obs_v3_file, tmp_path):
+    """Check the per-epoch flag and receiver clock offset columns"""
+    with open(obs_v3_file) as f:
+        lines = f.readlines()
+    # Clock offset on the first epoch, power failure flag on the second
+    lines[54] = lines[54].rstrip("\n") + "       0.000123456789\n"
+    lines[75] = lines[75].replace("30.0000000  0 20", "30.0000000  1 20")
+    path = tmp_path / "flags.rnx"
+    path.write_text("".join(lines))
+
+    for reader in (read_rinex_obs, read_rinex_obs_wide):
+        df, _, _ = reader(str(path))
+        per_epoch = df.group_by("epoch").agg(
+            pl.col("epoch_flag").first(), pl.col("clock_offset").first()
+        ).sort("epoch")
+
+        assert per_epoch["epoch_flag"].to_list()[:3] == [0, 1, 0]
+        assert per_epoch["clock_offset"][0] == pytest.approx(0.000123456789)
+        assert per_epoch["clock_offset"][1:].is_null().all()
@@ -0,0 +1,107 @@
+from datetime import datetime, timedelta, timezone
+
+import polars as pl
+from pytecggrs import select_ephemeris
+import pytest
+
+
+def _nav(rows):
+    return pl.DataFrame(
+        rows,
+        schema={
+            "toc": pl.Datetime("ns", "UTC"),
+            "time_system": pl.String,
+            "sv": pl.String,
+            "toe": pl.Float64,
+            "iode": pl.Float64,
+            "health": pl.Float64,
+            "t_tm": pl.Float64,
+        },
+        orient="row",
+    )
+
+
+def _epochs(sv, *hours):
+    day = datetime(2025, 3, 28, tzinfo=timezone.utc)
+    return pl.DataFrame(
+        {
+            "epoch": [day + timedelta(hours=h) for h in hours],
+            "sv": [sv] * len(hours),
+        }
+    )
+
+
+# 2025-03-28 is a Friday: 432000 s into GPS week 2359
+SOW = 432000.0
+DAY = datetime(2025, 3, 28, tzinfo=timezone.utc)
+
+
+def test_select_closest_toe():
+    """Check that the ephemeris with the closest toe is picked for each epoch"""
+    nav = _nav(
+        [
+            (DAY + timedelta(hours=h), "GPST", "G05", SOW + 3600 * h, 10 + h, 0.0, None)
+            for h in (0, 2, 4)
+        ]
+    )
+    ephem = select_ephemeris(nav, _epochs("G05", 0.5, 1.5, 3.9, 9.0))
+
+    assert ephem.columns[:3] == ["epoch", "sv", "dt"]
+    assert ephem["iode"].to_list() == [10.0, 12.0, 14.0, None]
+    assert ephem["dt"].to_list()[:3] == pytest.approx([1800.0, -1800.0, -360.0])
+
+
+def test_select_healthy_and_iode_change():
+    """Check that unhealthy records are skipped and the latest upload wins ties"""
+    nav = _nav(
+        [
+            (DAY, "GPST", "G07", SOW, 20.0, 1.0, SOW - 60),
+            (DAY, "GPST", "G07", SOW, 21.0, 0.0, SOW - 120),
+            (DAY, "GPST", "G07", SOW, 22.0, 0.0, SOW - 30),
+        ]
+    )
+    epochs = _epochs("G07", 0.25, 0.25)
+
+    ephem = select_ephemeris(nav, epochs)
+    assert ephem.height == 1
+    assert ephem["iode"].to_list() == [22.0]
+
+    nav = nav.with_columns(health=pl.Series([0.0, 1.0, 1.0]))
+    assert select_ephemeris(nav, epochs)["iode"].to_list() == [20.0]
+    assert select_ephemeris(nav, epochs, healthy_only=False)["iode"].to_list() == [22.0]
+
+
+def test_select_bdt_toe():
+    """Check that BDS toe is read in BDT weeks and compared in GPST"""
+    # BDT weeks start on Sundays too, and BDT is 14 s behind GPST
+    nav = _nav([(DAY, "BDT", "C20", SOW, 5.0, 0.0, None)])
+    ephem = select_ephemeris(nav, _epochs("C20", 1.0))
+
+    assert ephem["iode"].to_list() == [5.0]
+    assert ephem["dt"].item() == pytest.approx(3600.0 - 14.0)
+
+
+def test_select_qzss_fit_interval():
+    """Check that the QZSS fit interval flag selects a 2 h or a longer interval"""
+    nav = _nav([(DAY, "QZSST", "J02", SOW, 30.0, 0.0, None)])
+    epochs = _epochs("J02", 0.5, 1.5)
+
+    short = select_ephemeris(nav.with_columns(fitInt=pl.lit(0.0)), epochs)
+    assert short["iode"].to_list() == [30.0, None]
+
+    long = select_ephemeris(nav.with_columns(fitInt=pl.lit(1.0)), epochs)
+    assert long["iode"].to_list() == [30.0, 30.0]
+
+
+def test_select_glonasst_toc():
+    """Check that GLONASST toc is compared in GPST, and that unknown time systems are rejected"""
+    # GLONASST is UTC + 3 h, and GPST is 18 s ahead of UTC in 2025
+    toc = DAY + timedelta(hours=3, minutes=15, seconds=-18)
+    nav = _nav([(toc, "GLONASST", "R05", None, 7.0, 0.0, None)])
+    ephem = select_ephemeris(nav, _epochs("R05", 0.25))
+
+    assert ephem["iode"].to_list() == [7.0]
+    assert ephem["dt"].item() == pytest.approx(0.0)
+
+    with pytest.raises(ValueError):
+        select_ephemeris(nav.with_columns(time_system=pl.lit("XYZ")), _epochs("R05", 0.25))
@@ -0,0 +1,118 @@
+from datetime import datetime, timezone
+import gzip
+
+from pytecggrs import RinexParseError, WrongRinexTypeError, read_sp3
+import pytest
+
+
+def _position(sv, x, y, z, clock, exponents=(10, 11, 12, 120), flags="    "):
+    sx, sy, sz, sc = exponents
+    return (
+        f"P{sv}{x:14.6f}{y:14.6f}{z:14.6f}{clock:14.6f}"
+        f" {sx:2d} {sy:2d} {sz:2d} {sc:3d} {flags[:2]}  {flags[2:]}\n"
+    )
+
+
+def _sp3(data_type="P", version="d", body=""):
+    return (
+        f"#{version}{data_type}2024 10  7  0  0  0.00000000       2 ORBIT IGS20 FIT  IGS\n"
+        "## 2335  86400.00000000   300.00000000 60590 0.0000000000000\n"
+        "+    2   G01E11" + "  0" * 15 + "\n"
+        "++       10 12" + "  0" * 15 + "\n"
+        "%c M  cc GPS ccc cccc cccc cccc cccc ccccc ccccc ccccc ccccc\n"
+        "%c cc cc ccc ccc cccc cccc cccc cccc ccccc ccccc ccccc ccccc\n"
+        "%f  1.2500000  1.025000000  0.00000000000  0.000000000000000\n"
+        "%f  0.0000000  0.000000000  0.00000000000  0.000000000000000\n"
+        "/* FINAL ORBIT COMBINATION\n"
+        + body
+        + "EOF\n"
+    )
+
+
+BODY = (
+    "*  2024 10  7  0  0  0.00000000\n"
+    + _position("G01", 12345.678901, -23456.789012, 3456.789012, 123.456789)
+    + _position("E11", -1000.0, 2000.0, 29000.0, 999999.999999, flags="EPMP")
+    + "*  2024 10  7  0  5  0.00000000\n"
+    + _position("G01", 0.0, 0.0, 0.0, 123.5)
+)
+
+
+def test_read_sp3(tmp_path):
+    """Check SP3-d records and header"""
+    path = tmp_path / "IGS0OPSFIN_20242810000_01D_05M_ORB.SP3"
+    path.write_text(_sp3(body=BODY))
+
+    df, header = read_sp3(str(path))
+
+    assert df.columns[:3] == ["epoch", "time_system", "sv"]
+    assert "vx" not in df.columns
+    assert df["epoch"].to_list() == [
+        datetime(2024, 10, 7, 0, 0, tzinfo=timezone.utc),
+        datetime(2024, 10, 7, 0, 0, tzinfo=timezone.utc),
+        datetime(2024, 10, 7, 0, 5, tzinfo=timezone.utc),
+    ]
+
+    g01 = df.row(0, named=True)
+    assert g01["time_system"] == "GPST"
+    assert g01["x"] == pytest.approx(12345678.901)
+    assert g01["y"] == pytest.approx(-23456789.012)
+    assert g01["clock"] == pytest.approx(123.456789e-6)
+    assert g01["x_std"] == pytest.approx(1.25**10 * 1e-3)
+    assert g01["clock_std"] == pytest.approx(1.025**120 * 1e-12)
+    assert not g01["clock_event"]
+
+    e11 = df.row(1, named=True)
+    assert e11["clock"] is None
+    assert e11["clock_event"] and e11["clock_pred"]
+    assert e11["maneuver"] and e11["orbit_pred"]
+
+    # Missing positions are written as zeros
+    assert df.row(2, named=True)["x"] is None
+
+    assert header["version"] == "d"
+    assert header["start"] == "2024-10-07T00:00:00"
+    assert header["num_epochs"] == 2
+    assert header["coordinate_system"] == "IGS20"
+    assert header["orbit_type"] == "FIT"
+    assert header["agency"] == "IGS"
+    assert header["gps_week"] == 2335
+    assert header["epoch_interval"] == 300.0
+    assert header["time_system"] == "GPST"
+    assert header["satellites"] == ["G01", "E11"]
+    assert header["accuracy"]["E11"] == pytest.approx(2**12 * 1e-3)
+    assert header["comments"] == ["FINAL ORBIT COMBINATION"]
+
+
+def test_read_sp3_velocities_gzip(tmp_path):
+    """Check velocity records of a gzip-compressed SP3-c file"""
+    body = (
+        "*  2024 10  7  0  0  0.00000000\n"
+        + _position("G01", 12345.678901, -23456.789012, 3456.789012, 123.456789)
+        + f"VG01{-12345.678901:14.6f}{23456.789012:14.6f}{1234.56789:14.6f}{-1.234567:14.6f}\n"
+    )
+    path = tmp_path / "orbits.sp3.gz"
+    path.write_bytes(gzip.compress(_sp3("V", "c", body).encode()))
+
+    df, header = read_sp3(str(path))
+
+    assert header["version"] == "c"
+    assert header["data_type"] == "V"
+    g01 = df.row(0, named=True)
+    assert g01["vx"] == pytest.approx(-1234.5678901)
+    assert g01["vz"] == pytest.approx(123.456789)
+    assert g01["clock_rate"] == pytest.approx(-1.234567e-10)
+
+
+def test_read_sp3_errors(tmp_path, nav_v3_file):
+    """Check errors for non-SP3 files and unsupported versions"""
+    with pytest.raises(WrongRinexTypeError):
+        read_sp3(nav_v3_file)
+
+    path = tmp_path / "old.sp3"
+    path.write_text(_sp3(version="a", body=BODY))
+    with pytest.raises(RinexParseError):
+        read_sp3(str(path))
+
+    with pytest.raises(FileNotFoundError):
+        read_sp3(str(tmp_path / "missing.sp3"))
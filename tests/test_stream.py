@@ -0,0 +1,78 @@
+from pytecggrs import RinexObsReader, read_rinex_obs
+import polars as pl
+import pytest
+
+
+@pytest.mark.parametrize("fixture", ["obs_v2_file", "obs_v3_file"])
+def test_reader_matches_full_read(fixture, request):
+    """Check that concatenated chunks match a full read of the file"""
+    path = request.getfixturevalue(fixture)
+    df_full, coords, _ = read_rinex_obs(path)
+
+    reader = RinexObsReader(path, chunk_size=100)
+    chunks = list(reader)
+    assert len(chunks) > 1
+    assert all(chunk["epoch"].n_unique() <= 100 for chunk in chunks)
+    assert reader.rx_position == pytest.approx(coords)
+
+    df_stream = pl.concat(chunks)
+    assert df_stream.shape == df_full.shape
+    assert df_stream["epoch"].n_unique() == df_full["epoch"].n_unique()
+
+
+def test_reader_gzip(obs_v3_file):
+    """Check that gzip-compressed files can be streamed"""
+    import gzip
+    import shutil
+    import tempfile
+
+    with tempfile.NamedTemporaryFile(suffix=".rnx.gz") as tmp:
+        with open(obs_v3_file, "rb") as src, gzip.open(tmp.name, "wb") as dst:
+            shutil.copyfileobj(src, dst)
+        first = next(iter(RinexObsReader(tmp.name, chunk_size=10)))
+        assert first["epoch"].n_unique() == 10
+
+
+def test_reader_wide_and_filter(obs_v3_file):
+    """Check the wide output and filters of the streaming reader"""
+    reader = RinexObsReader(
+        obs_v3_file, chunk_size=500, wide=True, constellations=["G"]
+    )
+    for chunk in reader:
+        assert chunk.columns[:3] == ["epoch", "time_system", "sv"]
+        assert chunk["sv"].str.starts_with("G").all()
+
+
+def test_reader_header(obs_v3_file):
+    """Check that header metadata is available before iterating"""
+    reader = RinexObsReader(obs_v3_file)
+    assert reader.version.startswith("3")
+    assert reader.header["marker_name"] == "ASIR00ITA"
+
+
+def test_reader_hatanaka_not_supported(obs_v3_hatanaka_compressed_file):
+    """Check that Hatanaka-compressed files are rejected"""
+    with pytest.raises(ValueError):
+        RinexObsReader(obs_v3_hatanaka_compressed_file)
+
+
+def test_reader_invalid_chunk_size(obs_v3_file):
+    """Check that a ValueError is raised with an empty chunk size"""
+    with pytest.raises(ValueError):
+        RinexObsReader(obs_v3_file, chunk_size=0)
+
+
+def test_reader_stops_past_end(obs_v3_file, tmp_path):
+    """Check that the reader stops at the first epoch past the end of the time window"""
+    with open(obs_v3_file) as f:
+        lines = f.readlines()
+    # Corrupt the 00:01:30 epoch, which must never be parsed
+    assert lines[117].startswith("> 2024 10 07 00 01 30")
+    lines[117] = lines[117].replace("2024 10 07", "2024 1x 07")
+    path = tmp_path / "truncated_window.rnx"
+    path.write_text("".join(lines))
+
+    reader = RinexObsReader(str(path), chunk_size=1, end="2024-10-07T00:00:30 GPST")
+    chunks = list(reader)
+    assert len(chunks) == 2
+    assert next(reader, None) is None
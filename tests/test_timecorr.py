@@ -0,0 +1,62 @@
+from datetime import datetime, timezone
+
+from pytecggrs import read_rinex_nav_time
+import pytest
+
+
+def _header_line(content, label):
+    return f"{content:<60}{label:<20}\n"
+
+
+def test_time_corrections_v3(nav_v3_file):
+    """Check TIME SYSTEM CORR and LEAP SECONDS header records"""
+    corrections, leap = read_rinex_nav_time(nav_v3_file)
+
+    assert set(corrections["type"]) == {
+        "BDUT", "GAGP", "GAUT", "GLGP", "GLUT", "GPUT", "QZUT"
+    }
+    gaut = corrections.filter(type="GAUT").row(0, named=True)
+    assert gaut["source"] == "header"
+    assert gaut["a0"] == pytest.approx(9.3132257462e-10)
+    assert gaut["a1"] == 0.0
+    assert gaut["ref_time"] == 432000
+    assert gaut["ref_week"] == 2359
+
+    glgp = corrections.filter(type="GLGP").row(0, named=True)
+    assert glgp["a0"] == pytest.approx(0.8381903172e-08)
+
+    assert leap == {
+        "leap_seconds": 18,
+        "future_leap_seconds": 18,
+        "week": 1929,
+        "day": 7,
+        "time_system": "GPS",
+    }
+
+
+def test_time_corrections_sto_v4(tmp_path):
+    """Check RINEX 4 STO messages"""
+    content = (
+        _header_line("     4.00           N: GNSS NAV DATA    M: MIXED", "RINEX VERSION / TYPE")
+        + _header_line("    18", "LEAP SECONDS")
+        + _header_line("", "END OF HEADER")
+        + "> STO E05 IFNV\n"
+        + "    2022 06 10 00 00 00 GAUT               " + " " * 19 + "UTCGAL\n"
+        + "    " + "".join(f"{v:19.12E}" for v in [345600.0, 9.3e-10, -8.8e-16, 0.0]) + "\n"
+    )
+    path = tmp_path / "nav_v4.rnx"
+    path.write_text(content)
+
+    corrections, leap = read_rinex_nav_time(str(path))
+    sto = corrections.row(0, named=True)
+
+    assert sto["source"] == "message"
+    assert sto["type"] == "GAUT"
+    assert sto["sv"] == "E05"
+    assert sto["epoch"] == datetime(2022, 6, 10, tzinfo=timezone.utc)
+    assert sto["t_tm"] == 345600.0
+    assert sto["a0"] == pytest.approx(9.3e-10)
+    assert sto["a1"] == pytest.approx(-8.8e-16)
+    assert sto["utc_id"] == "UTCGAL"
+    assert leap["leap_seconds"] == 18
+    assert leap["future_leap_seconds"] is None